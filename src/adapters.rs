@@ -12,6 +12,29 @@ pub use flatten_die::FlattenDie;
 mod flat_map_die;
 pub use flat_map_die::FlatMapDie;
 
+mod retry;
+
+mod filter_die;
+pub use filter_die::FilterDie;
+
+mod filter_map_die;
+pub use filter_map_die::FilterMapDie;
+
+mod try_filter_die;
+pub use try_filter_die::TryFilterDie;
+
+mod try_filter_map_die;
+pub use try_filter_map_die::TryFilterMapDie;
+
+mod with_limit_die;
+pub use with_limit_die::WithLimitDie;
+
+mod scale_limit_die;
+pub use scale_limit_die::ScaleLimitDie;
+
+mod map_limit_die;
+pub use map_limit_die::MapLimitDie;
+
 mod boxed_die_once;
 pub use boxed_die_once::BoxedDieOnce;
 
@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+use crate::adapters::retry::{DEFAULT_MAX_TRIES, assert_max_tries, roll_with_retries};
+use crate::{Die, DieOnce, Fate};
+
+/// Adapter for [`Die::filter`].
+pub struct FilterDie<T, D, F> {
+    d: D,
+    f: F,
+    max_tries: u32,
+    label: Option<String>,
+    _t: PhantomData<T>,
+}
+
+impl<T, D, F> FilterDie<T, D, F> {
+    pub fn new(d: D, f: F) -> Self {
+        FilterDie {
+            d,
+            f,
+            max_tries: DEFAULT_MAX_TRIES,
+            label: None,
+            _t: PhantomData,
+        }
+    }
+
+    /// Sets the number of values that will be generated before giving up. The default is 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tries` is zero.
+    pub fn max_tries(self, max_tries: u32) -> Self {
+        assert_max_tries(max_tries);
+        FilterDie { max_tries, ..self }
+    }
+
+    /// Sets the label that is used in the panic message if the maximum number of tries is
+    /// reached. Without label the type name of the function is used, which is not descriptive
+    /// for closures.
+    pub fn label(self, label: impl Into<String>) -> Self {
+        FilterDie {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+}
+
+impl<T, D, F> DieOnce<T> for FilterDie<T, D, F>
+where
+    D: Die<T>,
+    F: Fn(&T) -> bool,
+{
+    fn roll_once(self, fate: Fate) -> T {
+        self.roll(fate)
+    }
+}
+
+impl<T, D, F> Die<T> for FilterDie<T, D, F>
+where
+    D: Die<T>,
+    F: Fn(&T) -> bool,
+{
+    fn roll(&self, fate: Fate) -> T {
+        let d = &self.d;
+        let f = &self.f;
+
+        roll_with_retries::<F, T>(fate, self.max_tries, self.label.as_deref(), |fate| {
+            let value = d.roll(fate);
+            if f(&value) { Some(value) } else { None }
+        })
+    }
+}
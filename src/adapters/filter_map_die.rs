@@ -0,0 +1,72 @@
+use std::marker::PhantomData;
+
+use crate::adapters::retry::{DEFAULT_MAX_TRIES, assert_max_tries, roll_with_retries};
+use crate::{Die, DieOnce, Fate};
+
+/// Adapter for [`Die::filter_map`].
+pub struct FilterMapDie<T, U, D, F> {
+    d: D,
+    f: F,
+    max_tries: u32,
+    label: Option<String>,
+    _t: PhantomData<T>,
+    _u: PhantomData<U>,
+}
+
+impl<T, U, D, F> FilterMapDie<T, U, D, F> {
+    pub fn new(d: D, f: F) -> Self {
+        FilterMapDie {
+            d,
+            f,
+            max_tries: DEFAULT_MAX_TRIES,
+            label: None,
+            _t: PhantomData,
+            _u: PhantomData,
+        }
+    }
+
+    /// Sets the number of values that will be generated before giving up. The default is 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tries` is zero.
+    pub fn max_tries(self, max_tries: u32) -> Self {
+        assert_max_tries(max_tries);
+        FilterMapDie { max_tries, ..self }
+    }
+
+    /// Sets the label that is used in the panic message if the maximum number of tries is
+    /// reached. Without label the type name of the function is used, which is not descriptive
+    /// for closures.
+    pub fn label(self, label: impl Into<String>) -> Self {
+        FilterMapDie {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+}
+
+impl<T, U, D, F> DieOnce<U> for FilterMapDie<T, U, D, F>
+where
+    D: Die<T>,
+    F: Fn(T) -> Option<U>,
+{
+    fn roll_once(self, fate: Fate) -> U {
+        self.roll(fate)
+    }
+}
+
+impl<T, U, D, F> Die<U> for FilterMapDie<T, U, D, F>
+where
+    D: Die<T>,
+    F: Fn(T) -> Option<U>,
+{
+    fn roll(&self, fate: Fate) -> U {
+        let d = &self.d;
+        let f = &self.f;
+
+        roll_with_retries::<F, U>(fate, self.max_tries, self.label.as_deref(), |fate| {
+            f(d.roll(fate))
+        })
+    }
+}
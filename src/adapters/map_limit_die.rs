@@ -0,0 +1,42 @@
+use std::marker::PhantomData;
+
+use crate::{Die, DieOnce, Fate, Limit};
+
+/// Adapter for [`Die::map_limit`].
+pub struct MapLimitDie<T, D, F> {
+    d: D,
+    f: F,
+    _t: PhantomData<T>,
+}
+
+impl<T, D, F> MapLimitDie<T, D, F> {
+    pub fn new(d: D, f: F) -> Self {
+        MapLimitDie {
+            d,
+            f,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T, D, F> DieOnce<T> for MapLimitDie<T, D, F>
+where
+    D: DieOnce<T>,
+    F: FnOnce(Limit) -> Limit,
+{
+    fn roll_once(self, mut fate: Fate) -> T {
+        let limit = (self.f)(fate.limit());
+        self.d.roll_once(fate.with_limit(limit))
+    }
+}
+
+impl<T, D, F> Die<T> for MapLimitDie<T, D, F>
+where
+    D: Die<T>,
+    F: Fn(Limit) -> Limit,
+{
+    fn roll(&self, mut fate: Fate) -> T {
+        let limit = (self.f)(fate.limit());
+        self.d.roll(fate.with_limit(limit))
+    }
+}
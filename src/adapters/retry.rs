@@ -0,0 +1,49 @@
+use std::any::type_name;
+
+use crate::{Fate, stat};
+
+// The default for the number of values the adapters will generate before giving up.
+pub(super) const DEFAULT_MAX_TRIES: u32 = 100;
+
+// Calls `try_roll` until it returns a value or `max_tries` is reached. The panic message
+// contains the label or, if there is none, the type name of the filter function `F`.
+pub(super) fn roll_with_retries<F, U>(
+    fate: Fate,
+    max_tries: u32,
+    label: Option<&str>,
+    try_roll: impl FnMut(Fate) -> Option<U>,
+) -> U {
+    match try_roll_with_retries(fate, max_tries, try_roll) {
+        Some(value) => value,
+        None => panic!(
+            "The filter `{}` rejected {} values in a row",
+            label.unwrap_or(type_name::<F>()),
+            max_tries
+        ),
+    }
+}
+
+// Calls `try_roll` until it returns a value or `max_tries` is reached. Returns `None` if
+// `max_tries` is reached.
+pub(super) fn try_roll_with_retries<U>(
+    mut fate: Fate,
+    max_tries: u32,
+    mut try_roll: impl FnMut(Fate) -> Option<U>,
+) -> Option<U> {
+    for rejections in 0..max_tries {
+        if let Some(value) = try_roll(fate.copy()) {
+            stat!("filter rejections", "{}", rejections);
+            return Some(value);
+        }
+    }
+
+    stat!("filter rejections", "{}", max_tries);
+    None
+}
+
+pub(super) fn assert_max_tries(max_tries: u32) {
+    assert!(
+        max_tries > 0,
+        "The maximum number of tries must not be zero"
+    );
+}
@@ -0,0 +1,54 @@
+use std::marker::PhantomData;
+
+use crate::{Die, DieOnce, Fate, Limit};
+
+/// Adapter for [`Die::scale_limit`].
+pub struct ScaleLimitDie<T, D> {
+    d: D,
+    factor: f64,
+    _t: PhantomData<T>,
+}
+
+impl<T, D> ScaleLimitDie<T, D> {
+    /// # Panics
+    ///
+    /// Panics if `factor` is negative or NaN.
+    #[track_caller]
+    pub fn new(d: D, factor: f64) -> Self {
+        assert!(
+            factor >= 0.0,
+            "The factor for scaling the limit must be non-negative, but is {factor}"
+        );
+
+        ScaleLimitDie {
+            d,
+            factor,
+            _t: PhantomData,
+        }
+    }
+
+    fn scale(&self, limit: Limit) -> Limit {
+        // The cast rounds down and saturates at the largest `u64`
+        Limit((limit.0 as f64 * self.factor) as u64)
+    }
+}
+
+impl<T, D> DieOnce<T> for ScaleLimitDie<T, D>
+where
+    D: DieOnce<T>,
+{
+    fn roll_once(self, mut fate: Fate) -> T {
+        let limit = self.scale(fate.limit());
+        self.d.roll_once(fate.with_limit(limit))
+    }
+}
+
+impl<T, D> Die<T> for ScaleLimitDie<T, D>
+where
+    D: Die<T>,
+{
+    fn roll(&self, mut fate: Fate) -> T {
+        let limit = self.scale(fate.limit());
+        self.d.roll(fate.with_limit(limit))
+    }
+}
@@ -0,0 +1,59 @@
+use std::marker::PhantomData;
+
+use crate::adapters::retry::{DEFAULT_MAX_TRIES, assert_max_tries, try_roll_with_retries};
+use crate::{Die, DieOnce, Fate};
+
+/// Adapter for [`Die::try_filter`].
+pub struct TryFilterDie<T, D, F> {
+    d: D,
+    f: F,
+    max_tries: u32,
+    _t: PhantomData<T>,
+}
+
+impl<T, D, F> TryFilterDie<T, D, F> {
+    pub fn new(d: D, f: F) -> Self {
+        TryFilterDie {
+            d,
+            f,
+            max_tries: DEFAULT_MAX_TRIES,
+            _t: PhantomData,
+        }
+    }
+
+    /// Sets the number of values that will be generated before giving up. The default is 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tries` is zero.
+    pub fn max_tries(self, max_tries: u32) -> Self {
+        assert_max_tries(max_tries);
+        TryFilterDie { max_tries, ..self }
+    }
+}
+
+impl<T, D, F> DieOnce<Option<T>> for TryFilterDie<T, D, F>
+where
+    D: Die<T>,
+    F: Fn(&T) -> bool,
+{
+    fn roll_once(self, fate: Fate) -> Option<T> {
+        self.roll(fate)
+    }
+}
+
+impl<T, D, F> Die<Option<T>> for TryFilterDie<T, D, F>
+where
+    D: Die<T>,
+    F: Fn(&T) -> bool,
+{
+    fn roll(&self, fate: Fate) -> Option<T> {
+        let d = &self.d;
+        let f = &self.f;
+
+        try_roll_with_retries(fate, self.max_tries, |fate| {
+            let value = d.roll(fate);
+            if f(&value) { Some(value) } else { None }
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use crate::adapters::retry::{DEFAULT_MAX_TRIES, assert_max_tries, try_roll_with_retries};
+use crate::{Die, DieOnce, Fate};
+
+/// Adapter for [`Die::try_filter_map`].
+pub struct TryFilterMapDie<T, U, D, F> {
+    d: D,
+    f: F,
+    max_tries: u32,
+    _t: PhantomData<T>,
+    _u: PhantomData<U>,
+}
+
+impl<T, U, D, F> TryFilterMapDie<T, U, D, F> {
+    pub fn new(d: D, f: F) -> Self {
+        TryFilterMapDie {
+            d,
+            f,
+            max_tries: DEFAULT_MAX_TRIES,
+            _t: PhantomData,
+            _u: PhantomData,
+        }
+    }
+
+    /// Sets the number of values that will be generated before giving up. The default is 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tries` is zero.
+    pub fn max_tries(self, max_tries: u32) -> Self {
+        assert_max_tries(max_tries);
+        TryFilterMapDie { max_tries, ..self }
+    }
+}
+
+impl<T, U, D, F> DieOnce<Option<U>> for TryFilterMapDie<T, U, D, F>
+where
+    D: Die<T>,
+    F: Fn(T) -> Option<U>,
+{
+    fn roll_once(self, fate: Fate) -> Option<U> {
+        self.roll(fate)
+    }
+}
+
+impl<T, U, D, F> Die<Option<U>> for TryFilterMapDie<T, U, D, F>
+where
+    D: Die<T>,
+    F: Fn(T) -> Option<U>,
+{
+    fn roll(&self, fate: Fate) -> Option<U> {
+        let d = &self.d;
+        let f = &self.f;
+
+        try_roll_with_retries(fate, self.max_tries, |fate| f(d.roll(fate)))
+    }
+}
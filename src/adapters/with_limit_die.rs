@@ -0,0 +1,38 @@
+use std::marker::PhantomData;
+
+use crate::{Die, DieOnce, Fate, Limit};
+
+/// Adapter for [`Die::with_limit`] and [`Die::limit_independent`].
+pub struct WithLimitDie<T, D> {
+    d: D,
+    limit: Limit,
+    _t: PhantomData<T>,
+}
+
+impl<T, D> WithLimitDie<T, D> {
+    pub fn new(d: D, limit: Limit) -> Self {
+        WithLimitDie {
+            d,
+            limit,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T, D> DieOnce<T> for WithLimitDie<T, D>
+where
+    D: DieOnce<T>,
+{
+    fn roll_once(self, mut fate: Fate) -> T {
+        self.d.roll_once(fate.with_limit(self.limit))
+    }
+}
+
+impl<T, D> Die<T> for WithLimitDie<T, D>
+where
+    D: Die<T>,
+{
+    fn roll(&self, mut fate: Fate) -> T {
+        self.d.roll(fate.with_limit(self.limit))
+    }
+}
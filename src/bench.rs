@@ -0,0 +1,353 @@
+//! Provides functions for measuring the throughput of generators.
+//!
+//! Slow generators reduce the number of test runs that are affordable. The function
+//! [`measure`] helps to find them. It rolls a [`Die`] many times with different [`Limit`]s
+//! and estimates how the time per value grows with the limit. The [`Limit`] docs recommend
+//! that the generation is in `O(limit)`, hence generators that grow super-linearly are
+//! flagged.
+
+use std::fmt;
+use std::hint::black_box;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use crate::{Die, Fate, Limit, Prng, Seed};
+
+// Exponents above this threshold are considered as super-linear. The threshold leaves
+// some room for measurement noise.
+const SUPER_LINEAR_EXPONENT: f64 = 1.5;
+
+/// The configuration for [`measure_with_config`].
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The smallest limit that will be measured.
+    pub start_limit: Limit,
+    /// The largest limit that will be measured.
+    pub end_limit: Limit,
+    /// The number of different limits between [`start_limit`] and [`end_limit`] that will be
+    /// measured. The limits are distributed geometrically.
+    ///
+    /// [`start_limit`]: Config::start_limit
+    /// [`end_limit`]: Config::end_limit
+    pub limit_count: usize,
+    /// Defines how many values will be generated for each limit.
+    pub rolls_per_limit: u64,
+    /// The seed for generating the values.
+    pub seed: Seed,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            start_limit: Limit(1),
+            end_limit: Limit(1000),
+            limit_count: 8,
+            rolls_per_limit: 1000,
+            seed: Seed(0x5EED),
+        }
+    }
+}
+
+/// The measurement for a single limit.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    /// The limit that was used for generating the values.
+    pub limit: Limit,
+    /// The number of generated values.
+    pub rolls: u64,
+    /// The total time for generating the values.
+    pub duration: Duration,
+}
+
+impl Measurement {
+    /// Returns the average time in nanoseconds for generating a single value.
+    pub fn nanos_per_value(&self) -> f64 {
+        self.duration.as_nanos() as f64 / self.rolls.max(1) as f64
+    }
+}
+
+/// The result of [`measure`].
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The measurements ordered by limit.
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    /// Estimates the exponent `k` assuming that the time per value grows like `limit^k`.
+    ///
+    /// The exponent is fitted with a linear regression of the logarithms. It's `None` if
+    /// there are less than two measurements with a positive limit and a positive time.
+    pub fn growth_exponent(&self) -> Option<f64> {
+        let points = self
+            .measurements
+            .iter()
+            .filter(|measurement| measurement.limit.0 > 0 && measurement.nanos_per_value() > 0.0)
+            .map(|measurement| {
+                let x = (measurement.limit.0 as f64).ln();
+                let y = measurement.nanos_per_value().ln();
+                (x, y)
+            })
+            .collect::<Vec<_>>();
+
+        if points.len() < 2 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let covariance = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum::<f64>();
+        let variance = points
+            .iter()
+            .map(|(x, _)| (x - mean_x).powi(2))
+            .sum::<f64>();
+
+        if variance == 0.0 {
+            None
+        } else {
+            Some(covariance / variance)
+        }
+    }
+
+    /// Returns whether the time per value seems to grow super-linearly with the limit.
+    ///
+    /// Such a generator violates the recommendation of [`Limit`] that the generation should
+    /// be in `O(limit)`.
+    pub fn is_super_linear(&self) -> bool {
+        self.growth_exponent()
+            .is_some_and(|exponent| exponent > SUPER_LINEAR_EXPONENT)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>12} {:>16}", "limit", "ns per value")?;
+        for measurement in &self.measurements {
+            writeln!(
+                f,
+                "{:>12} {:>16.1}",
+                measurement.limit.0,
+                measurement.nanos_per_value()
+            )?;
+        }
+        match self.growth_exponent() {
+            None => write!(f, "growth exponent: unknown"),
+            Some(exponent) => {
+                write!(f, "growth exponent: {exponent:.2}")?;
+                if self.is_super_linear() {
+                    write!(f, " (super-linear, should be at most 1)")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Measures the time for generating values with the given [`Die`].
+///
+/// The limits are taken from the given range. For other parameters the defaults of [`Config`]
+/// are used.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::bench;
+///
+/// let report = bench::measure(dice::vec(dice::u8(..), ..), 1..=100);
+/// println!("{report}");
+/// ```
+pub fn measure<T>(die: impl Die<T>, limit_range: RangeInclusive<u64>) -> Report {
+    let (start, end) = limit_range.into_inner();
+    let config = Config {
+        start_limit: Limit(start),
+        end_limit: Limit(end),
+        ..Config::default()
+    };
+    measure_with_config(die, config)
+}
+
+/// Measures the time for generating values with the given [`Die`] and [`Config`].
+///
+/// # Panics
+///
+/// Panics if [`Config::start_limit`] is greater than [`Config::end_limit`].
+pub fn measure_with_config<T>(die: impl Die<T>, config: Config) -> Report {
+    let limits = geometric_limits(config.start_limit, config.end_limit, config.limit_count);
+    let mut prng = Prng::from_seed(config.seed);
+
+    let measurements = limits
+        .into_iter()
+        .map(|limit| {
+            let start = Instant::now();
+            for _ in 0..config.rolls_per_limit {
+                let fate = Fate::new(&mut prng, limit);
+                black_box(die.roll(fate));
+            }
+            Measurement {
+                limit,
+                rolls: config.rolls_per_limit,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Report { measurements }
+}
+
+fn geometric_limits(start_limit: Limit, end_limit: Limit, count: usize) -> Vec<Limit> {
+    assert!(
+        start_limit <= end_limit,
+        "Start limit {} is greater than end limit {}",
+        start_limit.0,
+        end_limit.0
+    );
+
+    let (start, end) = (start_limit.0, end_limit.0);
+    let mut limits = match count {
+        0 => Vec::new(),
+        1 => vec![end],
+        _ => {
+            // Zero is not suitable for a geometric series, hence it's handled separately
+            let base = start.max(1) as f64;
+            let ratio = (end.max(1) as f64 / base).powf(1.0 / (count - 1) as f64);
+            let mut limits = (0..count)
+                .map(|index| (base * ratio.powi(index as i32)).round() as u64)
+                .map(|limit| limit.clamp(start, end))
+                .collect::<Vec<_>>();
+            limits[0] = start;
+            limits[count - 1] = end;
+            limits
+        }
+    };
+    limits.dedup();
+    limits.into_iter().map(Limit).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+    use std::time::Duration;
+
+    use crate::bench::{self, Config, Measurement, Report, geometric_limits};
+    use crate::prelude::*;
+    use crate::{Limit, Seed};
+
+    #[test]
+    fn geometric_limits_examples() {
+        let limits = |start, end, count| {
+            geometric_limits(Limit(start), Limit(end), count)
+                .into_iter()
+                .map(|limit| limit.0)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(limits(1, 1000, 4), vec![1, 10, 100, 1000]);
+        assert_eq!(limits(0, 100, 3), vec![0, 10, 100]);
+        assert_eq!(limits(5, 5, 3), vec![5]);
+        assert_eq!(limits(1, 10, 0), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn measure_returns_measurement_for_each_limit() {
+        let config = Config {
+            start_limit: Limit(1),
+            end_limit: Limit(100),
+            limit_count: 3,
+            rolls_per_limit: 10,
+            seed: Seed(42),
+        };
+        let report = bench::measure_with_config(dice::vec(dice::u8(..), ..), config);
+
+        let limits = report
+            .measurements
+            .iter()
+            .map(|measurement| measurement.limit.0)
+            .collect::<Vec<_>>();
+        assert_eq!(limits, vec![1, 10, 100]);
+        assert!(report.measurements.iter().all(|m| m.rolls == 10));
+    }
+
+    fn synthetic_report(nanos_per_value: impl Fn(u64) -> u64) -> Report {
+        let measurements = [10, 100, 1000, 10000]
+            .into_iter()
+            .map(|limit| Measurement {
+                limit: Limit(limit),
+                rolls: 10,
+                duration: Duration::from_nanos(10 * nanos_per_value(limit)),
+            })
+            .collect();
+        Report { measurements }
+    }
+
+    #[test]
+    fn growth_exponent_examples() {
+        let exponent = |nanos_per_value: fn(u64) -> u64| {
+            synthetic_report(nanos_per_value).growth_exponent().unwrap()
+        };
+
+        assert!((exponent(|_| 50) - 0.0).abs() < 1e-9);
+        assert!((exponent(|limit| 3 * limit) - 1.0).abs() < 1e-9);
+        assert!((exponent(|limit| limit * limit) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn growth_exponent_is_none_without_enough_measurements() {
+        let report = Report {
+            measurements: vec![
+                Measurement {
+                    limit: Limit(0),
+                    rolls: 10,
+                    duration: Duration::from_nanos(100),
+                },
+                Measurement {
+                    limit: Limit(10),
+                    rolls: 10,
+                    duration: Duration::from_nanos(100),
+                },
+                Measurement {
+                    limit: Limit(100),
+                    rolls: 10,
+                    duration: Duration::ZERO,
+                },
+            ],
+        };
+
+        assert_eq!(report.growth_exponent(), None);
+        assert!(!report.is_super_linear());
+    }
+
+    #[test]
+    fn is_super_linear_examples() {
+        assert!(!synthetic_report(|_| 50).is_super_linear());
+        assert!(!synthetic_report(|limit| 3 * limit).is_super_linear());
+        assert!(synthetic_report(|limit| limit * limit).is_super_linear());
+    }
+
+    #[test]
+    #[ignore = "depends on the timing of the machine"]
+    fn measure_flags_quadratic_die() {
+        let quadratic_die = dice::from_fn(|fate| {
+            let limit = fate.limit().0;
+            let mut sum = 0u64;
+            for i in 0..limit * limit {
+                sum = black_box(sum.wrapping_add(i));
+            }
+            sum
+        });
+        let config = Config {
+            start_limit: Limit(100),
+            end_limit: Limit(1000),
+            limit_count: 3,
+            rolls_per_limit: 5,
+            seed: Seed(42),
+        };
+        let report = bench::measure_with_config(quadratic_die, config);
+
+        assert!(report.is_super_linear(), "{report}");
+    }
+}
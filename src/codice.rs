@@ -26,6 +26,9 @@ where
 }
 
 /// Uses libstd's [`DefaultHasher`] to create a seed from a hashable value.
+///
+/// The algorithm of [`DefaultHasher`] may change between Rust versions, hence the seeds are not
+/// stable. Use [`stable`] if this is an issue.
 pub fn from_default_hasher<T: Hash>() -> impl Codie<T> {
     from_fn(|value: T| {
         let mut hasher = DefaultHasher::new();
@@ -34,6 +37,9 @@ pub fn from_default_hasher<T: Hash>() -> impl Codie<T> {
     })
 }
 
+mod stable;
+pub use stable::*;
+
 #[cfg(test)]
 mod tests {
     use crate::codice;
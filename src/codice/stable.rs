@@ -0,0 +1,343 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::Seed;
+use crate::codice;
+use crate::prelude::*;
+
+/// A hasher with a fixed algorithm.
+///
+/// In contrast to libstd's [`DefaultHasher`], its output is guaranteed to be stable across Rust
+/// versions and platforms. Together with [`StableHash`] it's used for implementing
+/// [`codice::stable`].
+///
+/// [`DefaultHasher`]: std::collections::hash_map::DefaultHasher
+/// [`codice::stable`]: crate::codice::stable
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    /// Creates a hasher with the initial state.
+    pub fn new() -> Self {
+        StableHasher {
+            state: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+
+    /// Feeds the given number into the hasher.
+    pub fn write_u64(&mut self, n: u64) {
+        self.state = mix(self.state ^ n).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    }
+
+    /// Feeds the given bytes into the hasher.
+    ///
+    /// The length is fed too, hence different byte slices are hashed differently even if
+    /// their concatenation is equal.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        for chunk in bytes.chunks(8) {
+            let mut number_bytes = [0; 8];
+            number_bytes[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(number_bytes));
+        }
+    }
+
+    /// Returns the hash of the values fed so far.
+    pub fn finish(&self) -> u64 {
+        mix(self.state)
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The finalizer of SplitMix64. It's a bijection with good avalanche properties.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Trait for types whose values can be hashed with [`StableHasher`].
+///
+/// The implementation must be structural, i.e. it must only depend on the fed values and
+/// their order. Values that are equal must be hashed equally.
+pub trait StableHash {
+    /// Feeds `self` into the given hasher.
+    fn stable_hash(&self, hasher: &mut StableHasher);
+}
+
+/// Summons a [`Codie`] for `T` based on [`StableHash`].
+///
+/// In contrast to [`codice::from_default_hasher`], the seeds are guaranteed to be stable
+/// across Rust versions and platforms. Hence functions generated with [`dice::fn_builder`]
+/// behave the same after a toolchain upgrade.
+///
+/// [`codice::from_default_hasher`]: crate::codice::from_default_hasher
+/// [`dice::fn_builder`]: crate::dice::fn_builder
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+///
+/// let codie = codice::stable::<(u8, &str)>();
+///
+/// assert_eq!(codie.coroll((42, "foo")), codie.coroll((42, "foo")));
+/// ```
+pub fn stable<T: StableHash>() -> impl Codie<T> {
+    codice::from_fn(|value: T| {
+        let mut hasher = StableHasher::new();
+        value.stable_hash(&mut hasher);
+        Seed(hasher.finish())
+    })
+}
+
+macro_rules! impl_stable_hash_for_integer {
+    ($($integer:ty)*) => {
+        $(
+            impl StableHash for $integer {
+                fn stable_hash(&self, hasher: &mut StableHasher) {
+                    // Signed integers are sign-extended, hence the hash doesn't depend on
+                    // the pointer width.
+                    hasher.write_u64(*self as i128 as u64);
+                }
+            }
+        )*
+    };
+}
+
+impl_stable_hash_for_integer! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+
+impl StableHash for u128 {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(*self as u64);
+        hasher.write_u64((*self >> 64) as u64);
+    }
+}
+
+impl StableHash for i128 {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        (*self as u128).stable_hash(hasher)
+    }
+}
+
+impl StableHash for bool {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(u64::from(*self))
+    }
+}
+
+impl StableHash for char {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(u64::from(*self))
+    }
+}
+
+impl StableHash for () {
+    fn stable_hash(&self, _hasher: &mut StableHasher) {}
+}
+
+impl StableHash for f32 {
+    /// All NaNs are hashed equally and `-0.0` is hashed like `0.0`.
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        f64::from(*self).stable_hash(hasher)
+    }
+}
+
+impl StableHash for f64 {
+    /// All NaNs are hashed equally and `-0.0` is hashed like `0.0`.
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        let canonical = if self.is_nan() {
+            f64::NAN
+        } else if *self == 0.0 {
+            0.0
+        } else {
+            *self
+        };
+        hasher.write_u64(canonical.to_bits())
+    }
+}
+
+impl StableHash for str {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_bytes(self.as_bytes())
+    }
+}
+
+impl StableHash for String {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.as_str().stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash> StableHash for [T] {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(self.len() as u64);
+        for elem in self {
+            elem.stable_hash(hasher);
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for Vec<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.as_slice().stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash, const N: usize> StableHash for [T; N] {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.as_slice().stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash> StableHash for Option<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        match self {
+            None => hasher.write_u64(0),
+            Some(value) => {
+                hasher.write_u64(1);
+                value.stable_hash(hasher);
+            }
+        }
+    }
+}
+
+impl<T: StableHash, E: StableHash> StableHash for Result<T, E> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        match self {
+            Ok(value) => {
+                hasher.write_u64(0);
+                value.stable_hash(hasher);
+            }
+            Err(error) => {
+                hasher.write_u64(1);
+                error.stable_hash(hasher);
+            }
+        }
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for &T {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        (**self).stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for &mut T {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        (**self).stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for Box<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        (**self).stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for Rc<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        (**self).stable_hash(hasher)
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for Arc<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        (**self).stable_hash(hasher)
+    }
+}
+
+macro_rules! impl_stable_hash_for_tuple {
+    ($($Ti:ident, $ti:ident)+) => {
+        impl<$($Ti: StableHash,)*> StableHash for ($($Ti,)*) {
+            fn stable_hash(&self, hasher: &mut StableHasher) {
+                let ($($ti,)*) = self;
+                $($ti.stable_hash(hasher);)*
+            }
+        }
+    };
+}
+
+impl_stable_hash_for_tuple! { T1, t1 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 T4, t4 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 T4, t4 T5, t5 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 T4, t4 T5, t5 T6, t6 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 T4, t4 T5, t5 T6, t6 T7, t7 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 T4, t4 T5, t5 T6, t6 T7, t7 T8, t8 }
+impl_stable_hash_for_tuple! { T1, t1 T2, t2 T3, t3 T4, t4 T5, t5 T6, t6 T7, t7 T8, t8 T9, t9 }
+
+#[cfg(test)]
+mod tests {
+    use crate::codice::{self, StableHash};
+    use crate::prelude::*;
+
+    fn seed_of<T: StableHash>(value: T) -> u64 {
+        codice::stable::<T>().coroll(value).0
+    }
+
+    #[test]
+    fn stable_examples() {
+        // These values must never change, otherwise generated functions are not stable.
+        assert_eq!(seed_of(0u8), 0xe587_d3df_f9e9_2ed0);
+        assert_eq!(seed_of("dicetest"), 0xd3ca_27f0_066d_2b14);
+        assert_eq!(seed_of((1u32, Some(-1i64))), 0x93f6_ad6f_3c2a_54c3);
+    }
+
+    #[test]
+    fn stable_is_equal_for_equal_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::vec(dice::string(dice::char(), ..), ..));
+            let other_value = value.clone();
+
+            assert_eq!(seed_of(value), seed_of(other_value));
+        })
+    }
+
+    #[test]
+    fn stable_is_independent_of_integer_type() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::i32(..));
+            assert_eq!(seed_of(value), seed_of(i64::from(value)));
+            assert_eq!(seed_of(value), seed_of(value as isize));
+        })
+    }
+
+    #[test]
+    fn stable_canonicalizes_floats() {
+        assert_eq!(seed_of(0.0f64), seed_of(-0.0f64));
+        assert_eq!(seed_of(f64::NAN), seed_of(-f64::NAN));
+        assert_eq!(seed_of(f32::NAN), seed_of(f64::NAN));
+        assert_ne!(seed_of(1.0f64), seed_of(-1.0f64));
+    }
+
+    #[test]
+    fn stable_distinguishes_structure() {
+        assert_ne!(seed_of(("ab", "c")), seed_of(("a", "bc")));
+        assert_ne!(
+            seed_of(vec![vec![1u8], vec![]]),
+            seed_of(vec![vec![], vec![1u8]])
+        );
+        assert_ne!(seed_of(Ok::<u8, u8>(1)), seed_of(Err::<u8, u8>(1)));
+        assert_ne!(seed_of(None::<u8>), seed_of(Some(0u8)));
+    }
+
+    #[test]
+    fn stable_calc_stats() {
+        Dicetest::repeatedly()
+            .passes(0)
+            .stats_enabled(true)
+            .run(|mut fate| {
+                let value = fate.roll(dice::u8(..));
+                stat!("codice::stable::<u8>() % 4", "{}", seed_of(value) % 4,);
+            })
+    }
+}
@@ -0,0 +1,119 @@
+use crate::Codie;
+use crate::codice::{self, StableHash};
+
+/// Provides a [`Codie`] implementation that converts values of `Self` into seeds.
+///
+/// This trait is the counterpart of [`Dice`]. It's used by [`dice::default_fn_builder`] for
+/// generating functions without passing a [`Codie`] for the input explicitly.
+///
+/// [`Dice`]: crate::Dice
+/// [`dice::default_fn_builder`]: crate::dice::default_fn_builder
+///
+/// `Codice` is implemented for all types that implement [`StableHash`]. The resulting [`Codie`]
+/// is the same as [`codice::stable`], i.e. a value has the same seed regardless of whether it's
+/// nested in another value or not.
+///
+/// # Implementing `Codice`
+///
+/// Structs and enums should implement [`StableHash`] by feeding their fields structurally. A
+/// derive macro for `Codice` like the one for [`Dice`] doesn't exist yet, hence the
+/// implementation must be written manually.
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::codice::{StableHash, StableHasher};
+///
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl StableHash for Point {
+///     fn stable_hash(&self, hasher: &mut StableHasher) {
+///         self.x.stable_hash(hasher);
+///         self.y.stable_hash(hasher);
+///     }
+/// }
+///
+/// let seed = codie::<Vec<Point>>().coroll(vec![Point { x: 1, y: 2 }]);
+/// ```
+///
+/// Types that can't implement [`StableHash`] can implement `Codice` directly, but can't be used
+/// as elements of the composite types.
+pub trait Codice: Sized {
+    /// Returns a [`Codie`] for `Self`. The seeds should be stable across Rust versions and
+    /// platforms.
+    fn codie() -> impl Codie<Self>;
+}
+
+/// Summons a [`Codie`] for `T` based on [`Codice`].
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+///
+/// let seed = codie::<Vec<u8>>().coroll(vec![1, 2, 3]);
+/// ```
+pub fn codie<T: Codice>() -> impl Codie<T> {
+    T::codie()
+}
+
+impl<T: StableHash> Codice for T {
+    fn codie() -> impl Codie<Self> {
+        codice::stable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn codie_is_deterministic() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::vec(dice::option(dice::u8(..)), ..));
+            let vec_codie = codie::<Vec<Option<u8>>>();
+
+            assert_eq!(vec_codie.coroll(value.clone()), vec_codie.coroll(value));
+        })
+    }
+
+    #[test]
+    fn codie_distinguishes_structure() {
+        let vec_codie = codie::<Vec<Vec<u8>>>();
+        assert_ne!(
+            vec_codie.coroll(vec![vec![1], vec![]]),
+            vec_codie.coroll(vec![vec![], vec![1]])
+        );
+
+        let tuple_codie = codie::<(Option<u8>, Option<u8>)>();
+        assert_ne!(
+            tuple_codie.coroll((Some(0), None)),
+            tuple_codie.coroll((None, Some(0)))
+        );
+
+        let result_codie = codie::<Result<u8, u8>>();
+        assert_ne!(result_codie.coroll(Ok(1)), result_codie.coroll(Err(1)));
+    }
+
+    #[test]
+    fn codie_equals_stable_codie() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::vec(dice::option(dice::u8(..)), ..));
+
+            let codie_seed = codie::<Vec<Option<u8>>>().coroll(value.clone());
+            let stable_seed = codice::stable::<Vec<Option<u8>>>().coroll(value);
+
+            assert_eq!(codie_seed, stable_seed);
+        })
+    }
+
+    #[test]
+    fn codie_for_box_is_transparent() {
+        assert_eq!(
+            codie::<Box<String>>().coroll(Box::new("foo".to_string())),
+            codie::<String>().coroll("foo".to_string())
+        );
+    }
+}
@@ -27,6 +27,9 @@ pub use integer::*;
 mod float;
 pub use float::*;
 
+mod distribution;
+pub use distribution::*;
+
 mod char;
 pub use self::char::*;
 
@@ -72,9 +75,15 @@ pub use b_tree_set::*;
 mod binary_heap;
 pub use binary_heap::*;
 
+mod bytes;
+pub use bytes::*;
+
 mod string;
 pub use string::*;
 
+mod pool;
+pub use pool::*;
+
 mod shuffle;
 pub use shuffle::*;
 
@@ -84,5 +93,11 @@ pub use split_vec::*;
 mod fn_builder;
 pub use fn_builder::*;
 
+mod structured_fn;
+pub use structured_fn::*;
+
+mod recursive;
+pub use recursive::*;
+
 mod index_of;
 pub use index_of::*;
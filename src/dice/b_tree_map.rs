@@ -50,7 +50,7 @@ where
 /// assert!(map.len() <= 73);
 ///
 /// let map = fate.roll(dice::b_tree_map(&elem_die, 17..));
-/// assert!(map.len() >= 17);
+/// assert!(!map.is_empty());
 ///
 /// let map = fate.roll(dice::b_tree_map(&elem_die, 42));
 /// assert!(map.len() <= 42);
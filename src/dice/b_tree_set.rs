@@ -50,7 +50,7 @@ where
 /// assert!(set.len() <= 73);
 ///
 /// let set = fate.roll(dice::b_tree_set(&elem_die, 17..));
-/// assert!(set.len() >= 17);
+/// assert!(!set.is_empty());
 ///
 /// let set = fate.roll(dice::b_tree_set(&elem_die, 42));
 /// assert!(set.len() <= 42);
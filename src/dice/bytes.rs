@@ -0,0 +1,189 @@
+use crate::dice::LengthRange;
+use crate::prelude::*;
+
+const SPECIAL_BYTES: [u8; 4] = [0x00, 0x7f, 0x80, 0xff];
+
+#[derive(Clone, Copy)]
+enum Injection {
+    None,
+    // Overwrites a few bytes at random positions with special bytes.
+    Sprinkle,
+    // Overwrites all bytes with the same special byte.
+    Fill,
+}
+
+fn inject_special_bytes(mut fate: Fate, bytes: &mut [u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    // Filled buffers distort the distribution of the bytes heavily, hence they are rare.
+    let injection = fate.roll(dice::weighted_one_of().three(
+        (27, Injection::None),
+        (4, Injection::Sprinkle),
+        (1, Injection::Fill),
+    ));
+
+    let special_byte_die = dice::one_of_slice(&SPECIAL_BYTES);
+
+    match injection {
+        Injection::None => (),
+        Injection::Sprinkle => {
+            // The number of special bytes is logarithmic in the length of the buffer, hence
+            // this is cheap even for huge buffers.
+            let count = fate.roll(dice::uni_usize(1..=bytes.len().ilog2() as usize + 1));
+            for _ in 0..count {
+                let index = fate.roll(dice::uni_usize(0..bytes.len()));
+                bytes[index] = fate.roll(&special_byte_die);
+            }
+        }
+        Injection::Fill => {
+            let special_byte = fate.roll(&special_byte_die);
+            bytes.fill(special_byte);
+        }
+    }
+}
+
+/// Generates a [`Vec`] of bytes.
+///
+/// In contrast to `dice::vec(dice::u8(..), length_range)` the bytes are generated in bulk with
+/// [`Fate::fill_bytes`], hence this generator is suitable for huge binary blobs. Once in a while
+/// some or all bytes will be replaced by special bytes like `0x00` or `0xff`.
+///
+/// The range specifies the length of the [`Vec`].
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let bytes = fate.with_limit(100.into()).roll(dice::bytes(..));
+/// assert!(bytes.len() <= 100);
+///
+/// let bytes = fate.roll(dice::bytes(1_000_000));
+/// assert!(bytes.len() == 1_000_000);
+/// ```
+pub fn bytes(length_range: impl LengthRange) -> impl Die<Vec<u8>> {
+    let length_die = dice::length(length_range);
+
+    dice::from_fn(move |mut fate| {
+        let length = fate.roll(&length_die);
+        let mut bytes = vec![0; length];
+        fate.fill_bytes(&mut bytes);
+        inject_special_bytes(fate, &mut bytes);
+        bytes
+    })
+}
+
+/// Generates a byte array.
+///
+/// In contrast to `dice::array(dice::u8(..))` the bytes are generated in bulk with
+/// [`Fate::fill_bytes`]. Once in a while some or all bytes will be replaced by special bytes
+/// like `0x00` or `0xff`.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let key: [u8; 32] = fate.roll(dice::byte_array());
+/// ```
+pub fn byte_array<const N: usize>() -> impl Die<[u8; N]> {
+    dice::from_fn(move |mut fate| {
+        let mut bytes = [0; N];
+        fate.fill_bytes(&mut bytes);
+        inject_special_bytes(fate, &mut bytes);
+        bytes
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dice::bytes::SPECIAL_BYTES;
+    use crate::prelude::*;
+
+    #[test]
+    fn bytes_generates_vec_that_satisfies_length_range() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let offset = fate.roll(dice::uni_usize(0..100));
+            let len = fate.roll(dice::uni_usize(0..100));
+            let bytes = fate.roll(dice::bytes(offset..=offset + len));
+
+            assert!(offset <= bytes.len() && bytes.len() <= offset + len);
+        })
+    }
+
+    #[test]
+    fn bytes_injects_special_bytes_sparingly() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let rolls = 4000;
+            let length = 64;
+            let bytes = (0..rolls)
+                .map(|_| fate.roll(dice::bytes(length)))
+                .collect::<Vec<_>>();
+
+            let fill_count = bytes
+                .iter()
+                .filter(|bytes| bytes.iter().all(|&byte| byte == bytes[0]))
+                .count();
+            let special_count = bytes
+                .iter()
+                .flatten()
+                .filter(|byte| SPECIAL_BYTES.contains(byte))
+                .count();
+            let special_ratio = special_count as f64 / (rolls * length) as f64;
+
+            hint_debug!(fill_count);
+            hint_debug!(special_ratio);
+
+            // About 1 of 32 buffers is filled
+            assert!((75..=175).contains(&fill_count));
+            // Uniformly distributed bytes would have a ratio of 1/64, the injections should add
+            // only about 4%
+            assert!((0.03..=0.08).contains(&special_ratio));
+        })
+    }
+
+    #[test]
+    fn bytes_calc_stats() {
+        Dicetest::repeatedly()
+            .passes(0)
+            .stats_enabled(true)
+            .run(|mut fate| {
+                let bytes = fate.roll(dice::bytes(8));
+                stat!(
+                    "number of zeros in dice::bytes(8)",
+                    "{}",
+                    bytes.iter().filter(|&&byte| byte == 0).count(),
+                );
+            })
+    }
+
+    #[test]
+    fn byte_array_calc_stats() {
+        Dicetest::repeatedly()
+            .passes(0)
+            .stats_enabled(true)
+            .run(|mut fate| {
+                stat!(
+                    "dice::byte_array::<2>()",
+                    "{:?}",
+                    fate.roll(dice::byte_array::<2>()),
+                );
+            })
+    }
+}
@@ -0,0 +1,780 @@
+use std::f64::consts::TAU;
+
+use crate::prelude::*;
+use crate::statistics::ln_gamma;
+
+// Generates a float that lies inside the half-open interval `(0, 1]`.
+fn positive_unit_f64(fate: &mut Fate) -> f64 {
+    1.0 - fate.roll(dice::open_unit_f64())
+}
+
+/// Generates a float that follows the normal distribution with the given mean and standard
+/// deviation.
+///
+/// The values are generated with the Box–Muller transform.
+///
+/// # Panics
+///
+/// Panics if `mean` is not finite or if `std_dev` is negative or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let body_height = fate.roll(dice::normal(170.0, 10.0));
+/// assert!(body_height.is_finite());
+/// ```
+#[track_caller]
+pub fn normal(mean: f64, std_dev: f64) -> impl Die<f64> {
+    assert!(mean.is_finite(), "The mean must be finite, but is {mean}");
+    assert!(
+        std_dev.is_finite() && std_dev >= 0.0,
+        "The standard deviation must be finite and non-negative, but is {std_dev}"
+    );
+
+    dice::from_fn(move |mut fate| {
+        let radius = (-2.0 * positive_unit_f64(&mut fate).ln()).sqrt();
+        let angle = TAU * fate.roll(dice::open_unit_f64());
+        mean + std_dev * radius * angle.cos()
+    })
+}
+
+/// Generates a float that follows the log-normal distribution, i.e. its logarithm follows the
+/// normal distribution with the given mean and standard deviation.
+///
+/// # Panics
+///
+/// Panics if `mean` is not finite or if `std_dev` is negative or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let file_size = fate.roll(dice::log_normal(8.0, 2.0));
+/// assert!(file_size >= 0.0);
+/// ```
+#[track_caller]
+pub fn log_normal(mean: f64, std_dev: f64) -> impl Die<f64> {
+    normal(mean, std_dev).map(f64::exp)
+}
+
+/// Generates a float that follows the exponential distribution with the given rate.
+///
+/// The mean of the distribution is `1 / rate`.
+///
+/// # Panics
+///
+/// Panics if `rate` is not positive or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let seconds_until_next_request = fate.roll(dice::exponential(0.5));
+/// assert!(seconds_until_next_request >= 0.0);
+/// ```
+#[track_caller]
+pub fn exponential(rate: f64) -> impl Die<f64> {
+    assert!(
+        rate.is_finite() && rate > 0.0,
+        "The rate must be finite and positive, but is {rate}"
+    );
+
+    dice::from_fn(move |mut fate| -positive_unit_f64(&mut fate).ln() / rate)
+}
+
+/// Generates the number of failures before the first success in a series of independent trials
+/// that succeed with probability `p`.
+///
+/// # Panics
+///
+/// Panics if `p` is not inside the half-open interval `(0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let retries = fate.roll(dice::geometric(0.9));
+/// assert!(retries < 1000);
+/// ```
+#[track_caller]
+pub fn geometric(p: f64) -> impl Die<u64> {
+    assert!(
+        p > 0.0 && p <= 1.0,
+        "The probability must be inside the interval (0, 1], but is {p}"
+    );
+
+    let ln_failure = (-p).ln_1p();
+
+    dice::from_fn(move |mut fate| {
+        if p == 1.0 {
+            0
+        } else {
+            // The cast rounds down and saturates
+            (positive_unit_f64(&mut fate).ln() / ln_failure) as u64
+        }
+    })
+}
+
+/// Generates an integer that follows the Poisson distribution with the given mean.
+///
+/// # Panics
+///
+/// Panics if `mean` is negative or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let arrivals_per_minute = fate.roll(dice::poisson(12.5));
+/// assert!(arrivals_per_minute < 1000);
+/// ```
+#[track_caller]
+pub fn poisson(mean: f64) -> impl Die<u64> {
+    assert!(
+        mean.is_finite() && mean >= 0.0,
+        "The mean must be finite and non-negative, but is {mean}"
+    );
+
+    dice::from_fn(move |mut fate| roll_poisson(&mut fate, mean))
+}
+
+/// Generates the number of successes in `n` independent trials that succeed with
+/// probability `p`.
+///
+/// # Panics
+///
+/// Panics if `p` is not inside the closed interval `[0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let heads = fate.roll(dice::binomial(100, 0.5));
+/// assert!(heads <= 100);
+/// ```
+#[track_caller]
+pub fn binomial(n: u64, p: f64) -> impl Die<u64> {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "The probability must be inside the interval [0, 1], but is {p}"
+    );
+
+    dice::from_fn(move |mut fate| roll_binomial(&mut fate, n, p))
+}
+
+// Above this size the Poisson and binomial distributions are reduced to smaller ones, see
+// Knuth, The Art of Computer Programming, Volume 2, Section 3.4.1.
+const SMALL_DISCRETE_SIZE: f64 = 1000.0;
+
+fn roll_poisson(fate: &mut Fate, mut mean: f64) -> u64 {
+    let mut offset = 0;
+
+    while mean > SMALL_DISCRETE_SIZE {
+        // The m-th arrival time of a Poisson process with rate 1
+        let m = (mean * 7.0 / 8.0).floor();
+        let arrival_time = roll_gamma(fate, m);
+        if arrival_time < mean {
+            offset += m as u64;
+            mean -= arrival_time;
+        } else {
+            return offset + roll_binomial(fate, m as u64 - 1, mean / arrival_time);
+        }
+    }
+
+    let mode = mean.floor();
+    let probability_of_mode = if mean == 0.0 {
+        1.0
+    } else {
+        (-mean + mode * mean.ln() - ln_gamma(mode + 1.0)).exp()
+    };
+    let u = fate.roll(dice::open_unit_f64());
+    let value = invert_from_mode(
+        u,
+        mode,
+        probability_of_mode,
+        f64::INFINITY,
+        |k| mean / (k + 1.0),
+        |k| k / mean,
+    );
+
+    offset + value
+}
+
+fn roll_binomial(fate: &mut Fate, mut n: u64, mut p: f64) -> u64 {
+    let mut offset = 0;
+
+    while n as f64 > SMALL_DISCRETE_SIZE && 0.0 < p && p < 1.0 {
+        // The i-th smallest of n uniformly distributed floats
+        let i = 1 + n / 2;
+        let order_statistic = roll_beta(fate, i as f64, (n - i + 1) as f64);
+        if order_statistic >= p {
+            n = i - 1;
+            p /= order_statistic;
+        } else {
+            offset += i;
+            n -= i;
+            p = (p - order_statistic) / (1.0 - order_statistic);
+        }
+        p = p.clamp(0.0, 1.0);
+    }
+
+    if p == 0.0 {
+        return offset;
+    }
+    if p == 1.0 {
+        return offset + n;
+    }
+
+    let n_float = n as f64;
+    let q = 1.0 - p;
+    let mode = ((n_float + 1.0) * p).floor().min(n_float);
+    let ln_binomial_coefficient =
+        ln_gamma(n_float + 1.0) - ln_gamma(mode + 1.0) - ln_gamma(n_float - mode + 1.0);
+    let probability_of_mode =
+        (ln_binomial_coefficient + mode * p.ln() + (n_float - mode) * q.ln()).exp();
+    let u = fate.roll(dice::open_unit_f64());
+    let value = invert_from_mode(
+        u,
+        mode,
+        probability_of_mode,
+        n_float,
+        |k| (n_float - k) / (k + 1.0) * (p / q),
+        |k| k / (n_float - k + 1.0) * (q / p),
+    );
+
+    offset + value
+}
+
+// Generates a float that follows the gamma distribution with the given shape and scale 1.
+//
+// It's assumed that `shape >= 1` holds. See Marsaglia and Tsang, A Simple Method for
+// Generating Gamma Variables.
+fn roll_gamma(fate: &mut Fate, shape: f64) -> f64 {
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    let normal_die = normal(0.0, 1.0);
+
+    loop {
+        let x = fate.roll(&normal_die);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = positive_unit_f64(fate);
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+// Generates a float that follows the beta distribution with the given shapes.
+//
+// It's assumed that both shapes are at least 1.
+fn roll_beta(fate: &mut Fate, alpha: f64, beta: f64) -> f64 {
+    let x = roll_gamma(fate, alpha);
+    let y = roll_gamma(fate, beta);
+    x / (x + y)
+}
+
+// Inverts the cumulative distribution function of a discrete unimodal distribution by
+// enumerating its values in the order `mode, mode - 1, mode + 1, mode - 2, mode + 2, ...`.
+// The expected number of steps is proportional to the standard deviation.
+//
+// The closures return the ratio between the probability of the next value and the probability
+// of `k` when moving up or down, respectively.
+fn invert_from_mode(
+    mut u: f64,
+    mode: f64,
+    probability_of_mode: f64,
+    max: f64,
+    ratio_up: impl Fn(f64) -> f64,
+    ratio_down: impl Fn(f64) -> f64,
+) -> u64 {
+    u -= probability_of_mode;
+    if u < 0.0 {
+        return mode as u64;
+    }
+
+    let (mut lower, mut lower_probability) = (mode, probability_of_mode);
+    let (mut upper, mut upper_probability) = (mode, probability_of_mode);
+
+    loop {
+        let can_move_down = lower > 0.0 && lower_probability > 0.0;
+        let can_move_up = upper < max && upper_probability > 0.0;
+
+        if !can_move_down && !can_move_up {
+            // The remaining probability is lost in rounding errors
+            return mode as u64;
+        }
+
+        if can_move_down {
+            lower_probability *= ratio_down(lower);
+            lower -= 1.0;
+            u -= lower_probability;
+            if u < 0.0 {
+                return lower as u64;
+            }
+        }
+
+        if can_move_up {
+            upper_probability *= ratio_up(upper);
+            upper += 1.0;
+            u -= upper_probability;
+            if u < 0.0 {
+                return upper as u64;
+            }
+        }
+    }
+}
+
+/// Generates a rank between `1` and `n` that follows the Zipf distribution with the given
+/// exponent, i.e. the probability of rank `k` is proportional to `k^-exponent`.
+///
+/// This is useful for generating power-law distributed data, e.g. word frequencies or
+/// popularity of items. The values are generated with rejection sampling.
+///
+/// # Panics
+///
+/// Panics if `n` is zero or if `exponent` is negative or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let words = ["the", "of", "and", "to", "a"];
+/// let rank = fate.roll(dice::zipf(words.len() as u64, 1.0));
+/// let word = words[rank as usize - 1];
+/// ```
+#[track_caller]
+pub fn zipf(n: u64, exponent: f64) -> impl Die<u64> {
+    assert!(n > 0, "The number of ranks must not be zero");
+    assert!(
+        exponent.is_finite() && exponent >= 0.0,
+        "The exponent must be finite and non-negative, but is {exponent}"
+    );
+
+    let n_float = n as f64;
+    // The envelope has the density `1` on `[0, 1]` and `x^-exponent` on `[1, n]`. This is the
+    // total mass of the envelope.
+    let total = if exponent == 1.0 {
+        1.0 + n_float.ln()
+    } else {
+        (n_float.powf(1.0 - exponent) - exponent) / (1.0 - exponent)
+    };
+    let inverse_envelope_cdf = move |p: f64| {
+        let mass = p * total;
+        if mass <= 1.0 {
+            mass
+        } else if exponent == 1.0 {
+            (mass - 1.0).exp()
+        } else {
+            (mass * (1.0 - exponent) + exponent).powf(1.0 / (1.0 - exponent))
+        }
+    };
+
+    dice::from_fn(move |mut fate| {
+        loop {
+            let x = inverse_envelope_cdf(fate.roll(dice::open_unit_f64()));
+            let rank = (x + 1.0).floor().min(n_float);
+
+            // The ratio between the probability of the rank and the density of the envelope
+            let mut ratio = rank.powf(-exponent);
+            if x > 1.0 {
+                ratio *= x.powf(exponent);
+            }
+
+            if fate.roll(dice::open_unit_f64()) < ratio {
+                return rank as u64;
+            }
+        }
+    })
+}
+
+/// Generates an index of the given weights. The probability of an index is proportional to its
+/// weight.
+///
+/// The values are generated in constant time with the alias method.
+///
+/// # Panics
+///
+/// Panics if the weights are empty, if a weight is negative or not finite or if the sum of the
+/// weights is zero.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let http_methods = ["GET", "POST", "PUT", "DELETE"];
+/// let index_die = dice::weighted_index([0.7, 0.2, 0.05, 0.05]);
+/// let http_method = http_methods[fate.roll(index_die)];
+/// ```
+#[track_caller]
+pub fn weighted_index(weights: impl IntoIterator<Item = f64>) -> impl Die<usize> {
+    let weights = weights.into_iter().collect::<Vec<_>>();
+    assert!(!weights.is_empty(), "The weights must not be empty");
+    assert!(
+        weights
+            .iter()
+            .all(|weight| weight.is_finite() && *weight >= 0.0),
+        "The weights must be finite and non-negative, but are {weights:?}"
+    );
+    let sum = weights.iter().sum::<f64>();
+    assert!(sum > 0.0, "The sum of the weights must not be zero");
+
+    // Vose's alias method
+    let len = weights.len();
+    let mut probabilities = weights
+        .iter()
+        .map(|weight| weight * len as f64 / sum)
+        .collect::<Vec<_>>();
+    let mut aliases = (0..len).collect::<Vec<_>>();
+    let (mut small, mut large): (Vec<_>, Vec<_>) =
+        (0..len).partition(|&index| probabilities[index] < 1.0);
+    while let (Some(small_index), Some(&large_index)) = (small.pop(), large.last()) {
+        aliases[small_index] = large_index;
+        probabilities[large_index] -= 1.0 - probabilities[small_index];
+        if probabilities[large_index] < 1.0 {
+            large.pop();
+            small.push(large_index);
+        }
+    }
+    // The remaining entries differ from 1 only because of rounding errors
+    for index in small.into_iter().chain(large) {
+        probabilities[index] = 1.0;
+    }
+
+    dice::from_fn(move |mut fate| {
+        let index = fate.roll(dice::uni_usize(0..len));
+        if fate.roll(dice::open_unit_f64()) < probabilities[index] {
+            index
+        } else {
+            aliases[index]
+        }
+    })
+}
+
+/// Generates a value by applying the inverse of a cumulative distribution function to a
+/// uniformly distributed float inside `[0, 1)`.
+///
+/// This allows to generate values of an arbitrary distribution if its quantile function is
+/// known.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// // The Pareto distribution with scale 1 and shape 2
+/// let pareto_die = dice::from_inverse_cdf(|p| (1.0 - p).powf(-1.0 / 2.0));
+/// assert!(fate.roll(pareto_die) >= 1.0);
+/// ```
+pub fn from_inverse_cdf<T>(inverse_cdf: impl Fn(f64) -> T) -> impl Die<T> {
+    dice::open_unit_f64().map(inverse_cdf)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::statistics::{self, erfc};
+
+    const SAMPLES: u64 = 10_000;
+
+    // The probabilities of the buckets `lower..=upper`. The first bucket contains all smaller
+    // values and the last bucket contains all larger values, hence no bucket has a tiny
+    // expected count.
+    fn buckets(lower: u64, upper: u64, probability: impl Fn(u64) -> f64) -> Vec<f64> {
+        let mut expected = vec![(0..=lower).map(&probability).sum::<f64>()];
+        expected.extend((lower + 1..upper).map(&probability));
+        expected.push(1.0 - expected.iter().sum::<f64>());
+        expected
+    }
+
+    fn bucket(lower: u64, upper: u64) -> impl Fn(&u64) -> usize {
+        move |&k| (k.clamp(lower, upper) - lower) as usize
+    }
+
+    fn factorial(n: u64) -> f64 {
+        (1..=n).map(|k| k as f64).product()
+    }
+
+    fn assert_p_value(p_value: f64) {
+        hint_debug!(p_value);
+        assert!(p_value > 1e-6);
+    }
+
+    #[test]
+    fn normal_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|fate| {
+            let cdf = |x: f64| 0.5 * erfc(-(x - 3.0) / (2.0 * 2f64.sqrt()));
+            let p_value =
+                statistics::kolmogorov_smirnov(fate, dice::normal(3.0, 2.0), SAMPLES, cdf);
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn log_normal_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|fate| {
+            let cdf = |x: f64| {
+                if x <= 0.0 {
+                    0.0
+                } else {
+                    0.5 * erfc(-x.ln() / 2f64.sqrt())
+                }
+            };
+            let p_value =
+                statistics::kolmogorov_smirnov(fate, dice::log_normal(0.0, 1.0), SAMPLES, cdf);
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn exponential_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|fate| {
+            let cdf = |x: f64| 1.0 - (-1.5 * x).exp();
+            let p_value =
+                statistics::kolmogorov_smirnov(fate, dice::exponential(1.5), SAMPLES, cdf);
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn geometric_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|fate| {
+            let p: f64 = 0.3;
+            let expected = buckets(0, 10, |k| (1.0 - p).powi(k as i32) * p);
+            let p_value = statistics::chi_squared(
+                fate,
+                dice::geometric(p),
+                SAMPLES,
+                &expected,
+                bucket(0, 10),
+            );
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn poisson_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let (mean, lower, upper): (f64, u64, u64) =
+                fate.roll(dice::one_of().three((0.5, 0, 3), (4.0, 0, 10), (15.0, 6, 26)));
+            hint_debug!(mean);
+
+            let expected = buckets(lower, upper, |k| {
+                (-mean).exp() * mean.powi(k as i32) / factorial(k)
+            });
+            let p_value = statistics::chi_squared(
+                fate,
+                dice::poisson(mean),
+                SAMPLES,
+                &expected,
+                bucket(lower, upper),
+            );
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn poisson_with_large_mean_is_plausible() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::poisson(1e9));
+            hint_debug!(value);
+            // The standard deviation is about 31623
+            assert!(value.abs_diff(1_000_000_000) < 500_000);
+        })
+    }
+
+    #[test]
+    fn binomial_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let (n, p, lower, upper): (u64, f64, u64, u64) = fate.roll(dice::one_of().three(
+                (10, 0.5, 1, 9),
+                (20, 0.1, 0, 6),
+                (30, 0.95, 25, 30),
+            ));
+            hint_debug!(n);
+            hint_debug!(p);
+
+            let binomial_coefficient = |k| factorial(n) / (factorial(k) * factorial(n - k));
+            let expected = buckets(lower, upper, |k| {
+                binomial_coefficient(k) * p.powi(k as i32) * (1.0 - p).powi((n - k) as i32)
+            });
+            let p_value = statistics::chi_squared(
+                fate,
+                dice::binomial(n, p),
+                SAMPLES,
+                &expected,
+                bucket(lower, upper),
+            );
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn large_binomial_and_poisson_have_expected_moments() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let (n, p) = (100_000, 0.3);
+            let mean = 50_000.0;
+            let samples = 2000;
+
+            let binomials = (0..samples)
+                .map(|_| fate.roll(dice::binomial(n, p)) as f64)
+                .collect::<Vec<_>>();
+            let poissons = (0..samples)
+                .map(|_| fate.roll(dice::poisson(mean)) as f64)
+                .collect::<Vec<_>>();
+
+            let moments = |values: &[f64]| {
+                let mean = values.iter().sum::<f64>() / values.len() as f64;
+                let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+                    / (values.len() - 1) as f64;
+                (mean, variance)
+            };
+            let (binomial_mean, binomial_variance) = moments(&binomials);
+            let (poisson_mean, poisson_variance) = moments(&poissons);
+
+            hint_debug!(binomial_mean);
+            hint_debug!(binomial_variance);
+            hint_debug!(poisson_mean);
+            hint_debug!(poisson_variance);
+
+            // The tolerances are about 6 standard errors
+            let expected_variance = n as f64 * p * (1.0 - p);
+            assert!(
+                (binomial_mean - n as f64 * p).abs() < 6.0 * (expected_variance / 2000.0).sqrt()
+            );
+            assert!((binomial_variance / expected_variance - 1.0).abs() < 0.2);
+            assert!((poisson_mean - mean).abs() < 6.0 * (mean / 2000.0).sqrt());
+            assert!((poisson_variance / mean - 1.0).abs() < 0.2);
+        })
+    }
+
+    #[test]
+    fn binomial_is_in_range() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let n = fate.roll(dice::u64(..));
+            let p = fate.roll(dice::unit_f64());
+            let value = fate.roll(dice::binomial(n, p));
+
+            hint_debug!(n);
+            hint_debug!(p);
+            hint_debug!(value);
+
+            assert!(value <= n);
+        })
+    }
+
+    #[test]
+    fn zipf_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let exponent = fate.roll(dice::one_of().three(0.0, 1.0, 2.5));
+            hint_debug!(exponent);
+
+            let n = 6;
+            let normalization = (1..=n).map(|k| (k as f64).powf(-exponent)).sum::<f64>();
+            let expected = (1..=n)
+                .map(|k| (k as f64).powf(-exponent) / normalization)
+                .collect::<Vec<_>>();
+            let p_value =
+                statistics::chi_squared(fate, dice::zipf(n, exponent), SAMPLES, &expected, |&k| {
+                    k as usize - 1
+                });
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn weighted_index_follows_distribution() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let weights = fate.roll(dice::vec(dice::u8(1..), 2..=20));
+            hint_debug!(weights);
+
+            let sum = weights.iter().map(|&weight| f64::from(weight)).sum::<f64>();
+            let expected = weights
+                .iter()
+                .map(|&weight| f64::from(weight) / sum)
+                .collect::<Vec<_>>();
+            let die = dice::weighted_index(weights.iter().map(|&weight| f64::from(weight)));
+            let p_value = statistics::chi_squared(fate, die, SAMPLES, &expected, |&index| index);
+            assert_p_value(p_value);
+        })
+    }
+
+    #[test]
+    fn weighted_index_never_generates_index_with_zero_weight() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let index = fate.roll(dice::weighted_index([0.0, 1.0, 0.0, 2.0, 0.0]));
+            assert!(index == 1 || index == 3);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "The sum of the weights must not be zero")]
+    fn weighted_index_panics_if_all_weights_are_zero() {
+        let _ = dice::weighted_index([0.0, 0.0]);
+    }
+
+    #[test]
+    fn distributions_fulfill_die_contract() {
+        crate::check_die(dice::normal(0.0, 1.0));
+        crate::check_die(dice::poisson(100.0));
+        crate::check_die(dice::binomial(1000, 0.3));
+        crate::check_die(dice::zipf(1000, 1.2));
+        crate::check_die(dice::weighted_index([1.0, 2.0, 3.0]));
+    }
+}
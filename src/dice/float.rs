@@ -3,6 +3,7 @@
 use std::fmt::Debug;
 use std::ops::{RangeFrom, RangeFull, RangeInclusive, RangeToInclusive};
 
+use crate::dice::integer::roll_edge_case_or_regular;
 use crate::prelude::*;
 
 /// Non-empty range for float generators [`dice::f32`] and [`dice::f64`].
@@ -86,6 +87,157 @@ macro_rules! impl_float_range {
 impl_float_range! { f32 }
 impl_float_range! { f64 }
 
+/// Float types that are supported by [`dice::float_builder`].
+///
+/// [`dice::float_builder`]: dice::float_builder()
+pub trait Float: Copy + PartialOrd + 'static {
+    /// The special values that are used by default, e.g. `0.0`, `-0.0` and infinity.
+    const SPECIAL_VALUES: &'static [Self];
+
+    /// Returns the inclusive lower bound and the inclusive upper bound of the type, i.e.
+    /// negative and positive infinity.
+    fn full_bounds() -> (Self, Self);
+
+    /// Generates a float inside `lower..=upper` that is neither infinite nor NaN if
+    /// `lower != upper`.
+    ///
+    /// It's assumed that `lower <= upper` holds and both bounds are not NaN.
+    fn roll_regular(fate: Fate, lower: Self, upper: Self) -> Self;
+}
+
+/// Builder for dice that generate floats with a tunable bias towards edge cases.
+///
+/// It's created with [`dice::float_builder`] and implements [`Die`]. With each roll it
+/// chooses randomly between
+///  - a regular value, i.e. a finite value inside the range,
+///  - a border value, i.e. the lower or upper bound of the range and
+///  - a special value that lies inside the range.
+///
+/// The choice is weighted with the regular weight, the border weight and the special weight.
+/// If all weights are zero, the choice falls back to a uniform choice between the three kinds.
+///
+/// [`dice::float_builder`]: dice::float_builder()
+#[derive(Debug, Clone)]
+pub struct FloatBuilder<F> {
+    lower: F,
+    upper: F,
+    special_values: Vec<F>,
+    regular_weight: u32,
+    border_weight: u32,
+    special_weight: u32,
+}
+
+impl<F: Float> FloatBuilder<F> {
+    /// Sets the range of the generated floats. By default, all floats except NaN are used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty or it contains NaN.
+    pub fn range(self, range: impl FloatRange<F>) -> Self {
+        let (lower, upper) = range.bounds();
+        FloatBuilder {
+            lower,
+            upper,
+            ..self
+        }
+    }
+
+    /// Replaces the special values, e.g. with domain-specific magic numbers.
+    ///
+    /// Special values outside the range are never generated. If such a value is chosen, a
+    /// regular value is generated instead.
+    pub fn special_values(self, special_values: impl IntoIterator<Item = F>) -> Self {
+        FloatBuilder {
+            special_values: special_values.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Sets the weight of regular values. The default weight is 10.
+    pub fn regular_weight(self, regular_weight: u32) -> Self {
+        FloatBuilder {
+            regular_weight,
+            ..self
+        }
+    }
+
+    /// Sets the weight of border values. The default weight is 1.
+    pub fn border_weight(self, border_weight: u32) -> Self {
+        FloatBuilder {
+            border_weight,
+            ..self
+        }
+    }
+
+    /// Sets the weight of special values. The default weight is 1.
+    pub fn special_weight(self, special_weight: u32) -> Self {
+        FloatBuilder {
+            special_weight,
+            ..self
+        }
+    }
+}
+
+impl<F: Float> DieOnce<F> for FloatBuilder<F> {
+    fn roll_once(self, fate: Fate) -> F {
+        self.roll(fate)
+    }
+}
+
+impl<F: Float> Die<F> for FloatBuilder<F> {
+    fn roll(&self, fate: Fate) -> F {
+        let (lower, upper) = (self.lower, self.upper);
+        roll_edge_case_or_regular(
+            fate,
+            (lower, upper),
+            &self.special_values,
+            [self.regular_weight, self.border_weight, self.special_weight],
+            dice::from_fn(|fate| F::roll_regular(fate, lower, upper)),
+        )
+    }
+}
+
+/// Creates a builder for a float [`Die`] with a tunable bias towards edge cases.
+///
+/// The builder uses the same defaults as [`dice::f32`] and [`dice::f64`]. Hence
+/// `dice::float_builder::<f64>().range(range)` generates the same values as
+/// `dice::f64(range)`.
+///
+/// [`dice::f32`]: dice::f32()
+/// [`dice::f64`]: dice::f64()
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// // Temperatures around the freezing and boiling point of water are likely
+/// let celsius_die = dice::float_builder::<f64>()
+///     .range(-273.15..=1000.0)
+///     .special_values([-0.1, 0.0, 0.1, 99.9, 100.0, 100.1])
+///     .border_weight(0)
+///     .special_weight(5);
+///
+/// let celsius = fate.roll(&celsius_die);
+/// assert!((-273.15..=1000.0).contains(&celsius));
+/// ```
+pub fn float_builder<F: Float>() -> FloatBuilder<F> {
+    let (lower, upper) = F::full_bounds();
+    FloatBuilder {
+        lower,
+        upper,
+        special_values: F::SPECIAL_VALUES.to_vec(),
+        regular_weight: 10,
+        border_weight: 1,
+        special_weight: 1,
+    }
+}
+
 macro_rules! fn_float {
     (
         $float:ident,
@@ -143,15 +295,19 @@ macro_rules! fn_float {
         /// let _float_die = dice::f32(f32::NAN);
         /// ```
         pub fn $float(range: impl FloatRange<$float>) -> impl Die<$float> {
-            // `FloatRange::bounds` guarantees that `lower <= upper` and both bounds are not NaN.
-            let (lower, upper) = range.bounds();
-
-            // Generates a value that is inside the range.
-            // If `lower != upper` the generator will not
-            // generate `INFINITY` or `NEG_INFINITY`. These
-            // values may be generates by the generator
-            // `maybe_special_value_die` instead.
-            let regular_value_die = dice::from_fn(move |mut fate| {
+            float_builder::<$float>().range(range)
+        }
+
+        impl Float for $float {
+            const SPECIAL_VALUES: &'static [Self] = $float_util::SPECIAL_VALUES;
+
+            fn full_bounds() -> (Self, Self) {
+                ($float::NEG_INFINITY, $float::INFINITY)
+            }
+
+            fn roll_regular(mut fate: Fate, lower: Self, upper: Self) -> Self {
+                // If `lower != upper` this will not generate `INFINITY` or `NEG_INFINITY`. These
+                // values may be generated as border values or special values instead.
                 if lower == upper {
                     // Range contains only one value
                     lower
@@ -161,7 +317,7 @@ macro_rules! fn_float {
                     let factor = fate.roll($unit_float());
 
                     // Just ignore infinite values here. If the range contains infinite values,
-                    // `maybe_special_value_die` is able to roll them.
+                    // they are generated as border values or special values.
 
                     let min = if lower == $float::NEG_INFINITY {
                         $float::MIN
@@ -178,33 +334,7 @@ macro_rules! fn_float {
 
                     $float_util::linear_ipol_float(factor, min, max)
                 }
-            });
-
-            // Generates once in a while a special value that is inside the range.
-            let maybe_special_value_die = {
-                let border_value_die = dice::one_of().two(Some(lower), Some(upper));
-                let const_value_die = {
-                    let const_values = $float_util::SPECIAL_VALUES;
-                    dice::one_of_slice(const_values).map(move |special_value| {
-                        if lower <= special_value && special_value <= upper {
-                            Some(special_value)
-                        } else {
-                            None
-                        }
-                    })
-                };
-
-                dice::weighted_one_of_die().three(
-                    (10, dice::just(None)),
-                    (1, border_value_die),
-                    (1, const_value_die),
-                )
-            };
-
-            dice::from_fn(move |mut fate| match fate.roll(&maybe_special_value_die) {
-                Some(special_value) => special_value,
-                None => fate.roll(&regular_value_die),
-            })
+            }
         }
 
         /// Generates an arbitrary float (including NaN). Some special floats have a higher
@@ -365,6 +495,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn float_builder_generates_custom_special_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let die = dice::float_builder::<f64>()
+                .range(0.0..=10.0)
+                .special_values([0.5, 2.5])
+                .regular_weight(0)
+                .border_weight(0);
+            let value = fate.roll(die);
+
+            hint_debug!(value);
+
+            assert!(value == 0.5 || value == 2.5);
+        })
+    }
+
+    #[test]
+    fn float_builder_generates_only_regular_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let die = dice::float_builder::<f32>()
+                .border_weight(0)
+                .special_weight(0);
+            let value = fate.roll(die);
+
+            hint_debug!(value);
+
+            assert!(value.is_finite());
+        })
+    }
+
     #[test]
     fn f64_util_open_unit_float_examples() {
         assert_eq!(0.0, f64_util::open_unit_float(0));
@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
 use crate::prelude::*;
@@ -50,6 +53,15 @@ where
         }
     }
 
+    /// Builds a function whose output depends on the input and on all previous calls.
+    ///
+    /// Each call reseeds the internal [`Prng`] with the seed of the input and generates the
+    /// output with it. The [`Prng`] is kept for the next call, hence the input and the output
+    /// of each call influence the outputs of all following calls.
+    ///
+    /// The function is still deterministic: two functions built from equal builders return
+    /// equal outputs if they are called with the same sequence of inputs. This can be used for
+    /// modelling stateful oracles.
     pub fn build_fn_mut(mut self) -> impl FnMut(I) -> O {
         move |input| {
             let output_die = &self.output_die;
@@ -61,8 +73,103 @@ where
             output_die.roll(fate)
         }
     }
+
+    /// Similar to [`FnBuilder::build_fn`], but adds a hint for each input the function is
+    /// called with. The hint contains the given name, the input and the output.
+    ///
+    /// Each input is only added once, hence the hints form a table of the observed function.
+    /// This helps to understand what the generated function did in a counterexample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let f = fate
+    ///     .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+    ///     .build_fn_with_hints("f");
+    ///
+    /// // Adds a hint like `f(42) = 7`, but only once
+    /// let x = f(42);
+    /// let y = f(42);
+    /// ```
+    pub fn build_fn_with_hints(self, name: impl Into<String>) -> impl Fn(I) -> O
+    where
+        I: Debug,
+        O: Debug,
+    {
+        let name = name.into();
+        let observed_inputs = RefCell::new(HashSet::new());
+        let f = self.build_fn();
+        move |input| {
+            let input_text = hints::enabled().then(|| format!("{input:?}"));
+            let output = f(input);
+            if let Some(input_text) = input_text {
+                let is_new_input = observed_inputs.borrow_mut().insert(input_text.clone());
+                if is_new_input {
+                    hints::add(|| format!("{name}({input_text}) = {output:?}"));
+                }
+            }
+            output
+        }
+    }
+
+    /// Similar to [`FnBuilder::build_fn_mut`], but adds a hint for each call of the function.
+    /// The hint contains the given name, the input and the output.
+    ///
+    /// In contrast to [`FnBuilder::build_fn_with_hints`] each call is added because the function
+    /// may return different outputs for the same input.
+    pub fn build_fn_mut_with_hints(self, name: impl Into<String>) -> impl FnMut(I) -> O
+    where
+        I: Debug,
+        O: Debug,
+    {
+        let name = name.into();
+        let mut f = self.build_fn_mut();
+        move |input| {
+            let input_text = hints::enabled().then(|| format!("{input:?}"));
+            let output = f(input);
+            if let Some(input_text) = input_text {
+                hints::add(|| format!("{name}({input_text}) = {output:?}"));
+            }
+            output
+        }
+    }
 }
 
+macro_rules! impl_build_fn_n {
+    ($build_fn_n:ident, $build_fn_mut_n:ident, $($Ti:ident, $ti:ident)+) => {
+        impl<$($Ti,)* O, IC, OD> FnBuilder<($($Ti,)*), O, IC, OD>
+        where
+            IC: Codie<($($Ti,)*)>,
+            OD: Die<O>,
+        {
+            /// Similar to [`FnBuilder::build_fn`], but the built function takes the elements of
+            /// the input tuple as separate arguments.
+            pub fn $build_fn_n(self) -> impl Fn($($Ti,)*) -> O {
+                let f = self.build_fn();
+                move |$($ti,)*| f(($($ti,)*))
+            }
+
+            /// Similar to [`FnBuilder::build_fn_mut`], but the built function takes the elements
+            /// of the input tuple as separate arguments.
+            pub fn $build_fn_mut_n(self) -> impl FnMut($($Ti,)*) -> O {
+                let mut f = self.build_fn_mut();
+                move |$($ti,)*| f(($($ti,)*))
+            }
+        }
+    };
+}
+
+impl_build_fn_n! { build_fn_2, build_fn_mut_2, T1, t1 T2, t2 }
+impl_build_fn_n! { build_fn_3, build_fn_mut_3, T1, t1 T2, t2 T3, t3 }
+impl_build_fn_n! { build_fn_4, build_fn_mut_4, T1, t1 T2, t2 T3, t3 T4, t4 }
+
 /// Generates a function builder.
 ///
 /// The builder can be converted into an implementation of [`FnOnce`], [`FnMut`] or [`Fn`].
@@ -79,7 +186,7 @@ where
 /// let mut fate = Fate::new(&mut prng, limit);
 ///
 /// let f = fate.roll(dice::fn_builder(
-///     codice::from_default_hasher(),
+///     codice::stable(),
 ///     dice::u8(..),
 /// )).build_fn_once();
 ///
@@ -96,7 +203,7 @@ where
 /// let mut fate = Fate::new(&mut prng, limit);
 ///
 /// let mut f = fate.roll(dice::fn_builder(
-///     codice::from_default_hasher(),
+///     codice::stable(),
 ///     dice::u8(..),
 /// )).build_fn_mut();
 ///
@@ -114,7 +221,7 @@ where
 /// let mut fate = Fate::new(&mut prng, limit);
 ///
 /// let f = fate.roll(dice::fn_builder(
-///     codice::from_default_hasher(),
+///     codice::stable(),
 ///     dice::u8(..),
 /// )).build_fn();
 ///
@@ -122,6 +229,25 @@ where
 /// let y = f(42);
 /// assert_eq!(x, y);
 /// ```
+///
+/// This example generates a [`Fn`] with two arguments:
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let f = fate.roll(dice::fn_builder(
+///     codice::stable(),
+///     dice::u8(..),
+/// )).build_fn_2();
+///
+/// let x = f(42, "foo");
+/// let y = f(42, "foo");
+/// assert_eq!(x, y);
+/// ```
 pub fn fn_builder<I, O, IC, OD>(
     input_codie: IC,
     output_die: OD,
@@ -139,3 +265,195 @@ where
         _o: PhantomData,
     })
 }
+
+/// Generates a function builder that uses the [`Codice`] implementation of the input type.
+///
+/// This is a shortcut for `dice::fn_builder(codie(), output_die)`, see [`dice::fn_builder`].
+///
+/// [`dice::fn_builder`]: dice::fn_builder()
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let f = fate.roll(dice::default_fn_builder(dice::u8(..))).build_fn();
+///
+/// let x = f((42, "foo"));
+/// let y = f((42, "foo"));
+/// assert_eq!(x, y);
+/// ```
+pub fn default_fn_builder<I, O, OD>(
+    output_die: OD,
+) -> impl DieOnce<FnBuilder<I, O, CodiceCodie<I>, OD>>
+where
+    I: Codice,
+    OD: DieOnce<O>,
+{
+    dice::fn_builder(CodiceCodie(PhantomData), output_die)
+}
+
+/// The [`Codie`] used by [`dice::default_fn_builder`]. It delegates to the [`Codice`]
+/// implementation of `I`.
+///
+/// [`dice::default_fn_builder`]: dice::default_fn_builder()
+pub struct CodiceCodie<I>(PhantomData<fn(I)>);
+
+impl<I: Codice> Codie<I> for CodiceCodie<I> {
+    fn coroll(&self, value: I) -> crate::Seed {
+        codie::<I>().coroll(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Prng;
+    use crate::hints;
+    use crate::prelude::*;
+
+    #[test]
+    fn build_fn_with_hints_adds_each_input_once() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let f = fate
+                .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+                .build_fn_with_hints("f");
+
+            let (outputs, hints) = hints::collect(|| [f(1), f(2), f(1)]);
+
+            if cfg!(feature = "hints") {
+                let texts = hints
+                    .0
+                    .into_iter()
+                    .map(|hint| hint.text)
+                    .collect::<Vec<_>>();
+                let expected = vec![
+                    format!("f(1) = {}", outputs[0]),
+                    format!("f(2) = {}", outputs[1]),
+                ];
+                assert_eq!(texts, expected);
+            }
+        })
+    }
+
+    #[test]
+    fn build_fn_mut_with_hints_adds_each_call() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let mut f = fate
+                .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+                .build_fn_mut_with_hints("g");
+
+            let (_, hints) = hints::collect(|| [f(1), f(1)]);
+
+            if cfg!(feature = "hints") {
+                assert_eq!(hints.0.len(), 2);
+            }
+        })
+    }
+
+    #[test]
+    fn build_fn_with_hints_behaves_like_build_fn() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.fork_prng();
+            let limit = fate.limit();
+
+            let mut prng_1 = prng.clone();
+            let f = Fate::new(&mut prng_1, limit)
+                .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+                .build_fn();
+            let mut prng_2 = prng;
+            let g = Fate::new(&mut prng_2, limit)
+                .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+                .build_fn_with_hints("g");
+
+            let input = fate.roll(dice::u64(..));
+            assert_eq!(f(input), g(input));
+        })
+    }
+
+    #[test]
+    fn build_fn_mut_is_deterministic() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.fork_prng();
+            let limit = fate.limit();
+            let build = |mut prng: Prng| {
+                Fate::new(&mut prng, limit)
+                    .roll(dice::fn_builder(codice::stable(), dice::u64(..)))
+                    .build_fn_mut()
+            };
+            let mut f = build(prng.clone());
+            let mut g = build(prng);
+
+            let inputs = fate.roll(dice::vec(dice::u8(..), ..));
+            for input in inputs {
+                assert_eq!(f(input), g(input));
+            }
+        })
+    }
+
+    #[test]
+    fn build_fn_mut_depends_on_call_history() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.fork_prng();
+            let limit = fate.limit();
+            let build = |mut prng: Prng| {
+                Fate::new(&mut prng, limit)
+                    .roll(dice::fn_builder(codice::stable(), dice::uni_u64(..)))
+                    .build_fn_mut()
+            };
+            let mut f = build(prng.clone());
+            let mut g = build(prng);
+
+            let prefix_f = fate.roll(dice::u8(..));
+            let prefix_g = fate.roll(dice::u8(..).filter(|&x| x != prefix_f));
+            let input = fate.roll(dice::u8(..));
+            hint_debug!(prefix_f);
+            hint_debug!(prefix_g);
+            hint_debug!(input);
+
+            f(prefix_f);
+            g(prefix_g);
+            assert_ne!(f(input), g(input));
+        })
+    }
+
+    #[test]
+    fn build_fn_mut_depends_on_own_output() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.fork_prng();
+            let limit = fate.limit();
+            let mut f = Fate::new(&mut prng.clone(), limit)
+                .roll(dice::fn_builder(codice::stable(), dice::uni_u64(..)))
+                .build_fn_mut();
+
+            let input = fate.roll(dice::u8(..));
+            hint_debug!(input);
+
+            assert_ne!(f(input), f(input));
+        })
+    }
+
+    #[test]
+    fn build_fn_2_behaves_like_build_fn() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.fork_prng();
+            let limit = fate.limit();
+
+            let mut prng_1 = prng.clone();
+            let f = Fate::new(&mut prng_1, limit)
+                .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+                .build_fn();
+            let mut prng_2 = prng;
+            let g = Fate::new(&mut prng_2, limit)
+                .roll(dice::fn_builder(codice::stable(), dice::u8(..)))
+                .build_fn_2();
+
+            let (x, y) = fate.roll(dice::zip().two(dice::u64(..), dice::bool()));
+            assert_eq!(f((x, y)), g(x, y));
+        })
+    }
+}
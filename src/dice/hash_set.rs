@@ -72,7 +72,7 @@ where
 /// assert!(set.len() <= 73);
 ///
 /// let set = fate.roll(dice::hash_set(&elem_die, 17..));
-/// assert!(set.len() >= 17);
+/// assert!(!set.is_empty());
 ///
 /// let set = fate.roll(dice::hash_set(&elem_die, 42));
 /// assert!(set.len() <= 42);
@@ -108,6 +108,195 @@ impl_integer_range! { i128 }
 impl_integer_range! { usize }
 impl_integer_range! { isize }
 
+/// Integer types that are supported by [`dice::integer_builder`].
+///
+/// [`dice::integer_builder`]: dice::integer_builder()
+pub trait Integer: Copy + PartialOrd + 'static {
+    /// The special values that are used by default, e.g. `1` and `2`.
+    const SPECIAL_VALUES: &'static [Self];
+
+    /// Returns the inclusive lower bound and the inclusive upper bound of the type.
+    fn full_bounds() -> (Self, Self);
+
+    /// Generates a uniformly distributed integer inside `lower..=upper`.
+    ///
+    /// It's assumed that `lower <= upper` holds.
+    fn roll_uniform(fate: Fate, lower: Self, upper: Self) -> Self;
+}
+
+/// Builder for dice that generate integers with a tunable bias towards edge cases.
+///
+/// It's created with [`dice::integer_builder`] and implements [`Die`]. With each roll it
+/// chooses randomly between
+///  - a regular value, i.e. a uniformly distributed value inside the range,
+///  - a border value, i.e. the lower or upper bound of the range and
+///  - a special value that lies inside the range.
+///
+/// The choice is weighted with the regular weight, the border weight and the special weight.
+/// If all weights are zero, the choice falls back to a uniform choice between the three kinds.
+///
+/// [`dice::integer_builder`]: dice::integer_builder()
+#[derive(Debug, Clone)]
+pub struct IntegerBuilder<I> {
+    lower: I,
+    upper: I,
+    special_values: Vec<I>,
+    regular_weight: u32,
+    border_weight: u32,
+    special_weight: u32,
+}
+
+impl<I: Integer> IntegerBuilder<I> {
+    /// Sets the range of the generated integers. By default, the full range of the type is
+    /// used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn range(self, range: impl IntegerRange<I>) -> Self {
+        let (lower, upper) = range.bounds();
+        IntegerBuilder {
+            lower,
+            upper,
+            ..self
+        }
+    }
+
+    /// Replaces the special values, e.g. with protocol limits or domain-specific magic numbers.
+    ///
+    /// Special values outside the range are never generated. If such a value is chosen, a
+    /// regular value is generated instead.
+    pub fn special_values(self, special_values: impl IntoIterator<Item = I>) -> Self {
+        IntegerBuilder {
+            special_values: special_values.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Sets the weight of regular values. The default weight is 6.
+    pub fn regular_weight(self, regular_weight: u32) -> Self {
+        IntegerBuilder {
+            regular_weight,
+            ..self
+        }
+    }
+
+    /// Sets the weight of border values. The default weight is 1.
+    pub fn border_weight(self, border_weight: u32) -> Self {
+        IntegerBuilder {
+            border_weight,
+            ..self
+        }
+    }
+
+    /// Sets the weight of special values. The default weight is 1.
+    pub fn special_weight(self, special_weight: u32) -> Self {
+        IntegerBuilder {
+            special_weight,
+            ..self
+        }
+    }
+}
+
+impl<I: Integer> DieOnce<I> for IntegerBuilder<I> {
+    fn roll_once(self, fate: Fate) -> I {
+        self.roll(fate)
+    }
+}
+
+impl<I: Integer> Die<I> for IntegerBuilder<I> {
+    fn roll(&self, fate: Fate) -> I {
+        let (lower, upper) = (self.lower, self.upper);
+        roll_edge_case_or_regular(
+            fate,
+            (lower, upper),
+            &self.special_values,
+            [self.regular_weight, self.border_weight, self.special_weight],
+            dice::from_fn(|fate| I::roll_uniform(fate, lower, upper)),
+        )
+    }
+}
+
+/// Creates a builder for an integer [`Die`] with a tunable bias towards edge cases.
+///
+/// The builder uses the same defaults as [`dice::u8`], [`dice::i32`], etc. Hence
+/// `dice::integer_builder::<u8>().range(range)` generates the same values as
+/// `dice::u8(range)`.
+///
+/// [`dice::u8`]: dice::u8()
+/// [`dice::i32`]: dice::i32()
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// // Powers of two and their neighbors are likely
+/// let special_values = (1..16).flat_map(|n| {
+///     let power = 2i32.pow(n);
+///     [power - 1, power, power + 1]
+/// });
+/// let port_die = dice::integer_builder::<i32>()
+///     .range(0..=65535)
+///     .special_values(special_values)
+///     .special_weight(4);
+///
+/// let port = fate.roll(&port_die);
+/// assert!((0..=65535).contains(&port));
+/// ```
+pub fn integer_builder<I: Integer>() -> IntegerBuilder<I> {
+    let (lower, upper) = I::full_bounds();
+    IntegerBuilder {
+        lower,
+        upper,
+        special_values: I::SPECIAL_VALUES.to_vec(),
+        regular_weight: 6,
+        border_weight: 1,
+        special_weight: 1,
+    }
+}
+
+// Generates once in a while a border value or a special value that is inside the range,
+// otherwise a regular value.
+pub(super) fn roll_edge_case_or_regular<N: Copy + PartialOrd>(
+    mut fate: Fate,
+    (lower, upper): (N, N),
+    special_values: &[N],
+    [regular_weight, border_weight, special_weight]: [u32; 3],
+    regular_value_die: impl Die<N>,
+) -> N {
+    let maybe_special_value_die = {
+        let border_value_die = dice::one_of().two(Some(lower), Some(upper));
+        let const_value_die = dice::from_fn(|mut fate| {
+            if special_values.is_empty() {
+                return None;
+            }
+            let special_value = fate.roll(dice::one_of_slice(special_values));
+            if lower <= special_value && special_value <= upper {
+                Some(special_value)
+            } else {
+                None
+            }
+        });
+
+        dice::weighted_one_of_die().three(
+            (regular_weight, dice::just(None)),
+            (border_weight, border_value_die),
+            (special_weight, const_value_die),
+        )
+    };
+
+    match fate.roll(&maybe_special_value_die) {
+        Some(special_value) => special_value,
+        None => fate.roll(&regular_value_die),
+    }
+}
+
 macro_rules! fn_integer {
     (
         $integer:ident,
@@ -254,37 +443,21 @@ macro_rules! fn_integer {
         /// let _integer_die = dice::u8(71..42);
         /// ```
         pub fn $integer(range: impl IntegerRange<$integer>) -> impl Die<$integer> {
-            let (lower, upper) = range.bounds();
+            integer_builder::<$integer>().range(range)
+        }
 
-            // `uni_integer` does not need to check the range again
-            let unchecked_range = UncheckedRange { lower, upper };
-            let regular_value_die = $uni_integer(unchecked_range);
-
-            // Generates once in a while a special value that is inside the range
-            let maybe_special_value_die = {
-                let border_value_die = dice::one_of().two(Some(lower), Some(upper));
-                let const_value_die = {
-                    let special_values = &$special_values;
-                    dice::one_of_slice(special_values).map(move |special_value| {
-                        if lower <= special_value && special_value <= upper {
-                            Some(special_value)
-                        } else {
-                            None
-                        }
-                    })
-                };
-
-                dice::weighted_one_of_die().three(
-                    (6, dice::just(None)),
-                    (1, border_value_die),
-                    (1, const_value_die),
-                )
-            };
-
-            dice::from_fn(move |mut fate| match fate.roll(&maybe_special_value_die) {
-                Some(special_value) => special_value,
-                None => fate.roll(&regular_value_die),
-            })
+        impl Integer for $integer {
+            const SPECIAL_VALUES: &'static [Self] = &$special_values;
+
+            fn full_bounds() -> (Self, Self) {
+                ($integer::MIN, $integer::MAX)
+            }
+
+            fn roll_uniform(fate: Fate, lower: Self, upper: Self) -> Self {
+                // `uni_integer` does not need to check the range again
+                let unchecked_range = UncheckedRange { lower, upper };
+                $uni_integer(unchecked_range).roll(fate)
+            }
         }
     };
 }
@@ -319,6 +492,7 @@ mod tests {
     use std::fmt::Debug;
 
     use crate::prelude::*;
+    use crate::{Limit, Seed, statistics};
 
     fn range_contains_integer<I, ID, B, BD, R>(
         mut fate: Fate,
@@ -572,6 +746,116 @@ mod tests {
             })
     }
 
+    #[test]
+    fn uni_u8_is_uniform() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let expected = [1.0 / 16.0; 16];
+            let p_value =
+                statistics::chi_squared(fate.copy(), dice::uni_u8(..), 10_000, &expected, |&x| {
+                    usize::from(x >> 4)
+                });
+            hint_debug!(p_value);
+            assert!(p_value > 1e-6);
+
+            let expected = [1.0 / 10.0; 10];
+            let p_value =
+                statistics::chi_squared(fate, dice::uni_u8(100..=109), 10_000, &expected, |&x| {
+                    usize::from(x - 100)
+                });
+            hint_debug!(p_value);
+            assert!(p_value > 1e-6);
+        })
+    }
+
+    #[test]
+    fn integer_builder_generates_custom_special_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let special_values = fate.roll(dice::vec(dice::i32(..), 1..));
+            let die = dice::integer_builder::<i32>()
+                .special_values(special_values.clone())
+                .regular_weight(0)
+                .border_weight(0);
+            let value = fate.roll(die);
+
+            hint_debug!(special_values);
+            hint_debug!(value);
+
+            assert!(special_values.contains(&value));
+        })
+    }
+
+    #[test]
+    fn integer_builder_ignores_special_values_outside_range() {
+        Dicetest::once().run(|mut fate| {
+            let die = dice::integer_builder::<u16>()
+                .range(10..=20)
+                .special_values([0, 15, 1000])
+                .regular_weight(0)
+                .border_weight(0);
+
+            let values = (0..3000).map(|_| fate.roll(&die)).collect::<Vec<_>>();
+            assert!(values.iter().all(|value| (10..=20).contains(value)));
+
+            // A third of the rolls choose 15, the others fall back to a regular value that is 15
+            // with probability 1/11, hence 15 is expected in 3000 * (1/3 + 2/3 * 1/11) = 1182 rolls
+            let count_15 = values.iter().filter(|&&value| value == 15).count();
+            hint_debug!(count_15);
+            assert!((1080..=1280).contains(&count_15));
+        })
+    }
+
+    #[test]
+    fn integer_builder_generates_only_border_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let die = dice::integer_builder::<i64>()
+                .range(-7..=7)
+                .regular_weight(0)
+                .special_weight(0);
+            let value = fate.roll(die);
+            assert!(value == -7 || value == 7);
+        })
+    }
+
+    #[test]
+    fn integer_builder_with_defaults_equals_preset() {
+        // The first values of a fixed seed
+        fn first_values<I: Into<i128>>(die: impl Die<I>) -> Vec<i128> {
+            die.samples(Seed(0x5EED), Limit(100))
+                .take(10)
+                .map(Into::into)
+                .collect()
+        }
+
+        // The values were generated by the presets before the builder was introduced
+        let u8_values = [154, 87, 87, 3, 151, 169, 85, 146, 3, 188];
+        let i64_values = [
+            6691602762653310369,
+            -968977569087341811,
+            0,
+            -4365918347021114498,
+            -1325559281822308595,
+            1,
+            -5629289906640697572,
+            9223372036854775807,
+            -7435504841467681538,
+            -1773879815071974593,
+        ];
+        let u32_values = [868, 19, 1, 402, 955, 1, 742, 1000, 457, 33];
+
+        assert_eq!(first_values(dice::u8(3..200)), u8_values);
+        assert_eq!(
+            first_values(dice::integer_builder::<u8>().range(3..200)),
+            u8_values
+        );
+        assert_eq!(first_values(dice::i64(..)), i64_values);
+        assert_eq!(first_values(dice::integer_builder::<i64>()), i64_values);
+        assert_eq!(first_values(dice::u32(..=1000)), u32_values);
+        assert_eq!(
+            first_values(dice::integer_builder::<u32>().range(..=1000)),
+            u32_values
+        );
+    }
+
     #[test]
     fn i8_calc_stats() {
         Dicetest::repeatedly()
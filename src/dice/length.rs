@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
+use crate::dice::integer::roll_edge_case_or_regular;
 use crate::prelude::*;
 
 /// Non-empty range for [`dice::length`].
@@ -82,10 +83,24 @@ impl LengthRange for RangeToInclusive<usize> {
     }
 }
 
+// Returns the inclusive upper bound of the length, the limit is used if the range has no upper
+// bound.
+fn length_upper_bound(lower: usize, upper_opt: Option<usize>, fate: &Fate) -> usize {
+    upper_opt.unwrap_or_else(|| lower.saturating_add(fate.limit().saturating_to_usize()))
+}
+
 /// Generates a random length that can be used for collections, etc. The length is bounded by the
 /// given range and the [`Limit`] parameter passed to [`Die::roll`].
 ///
+/// Empty and small collections are a common source of bugs, hence the lengths are biased
+/// towards edge cases in the style of [`dice::usize`]. Once in a while the length `0`, the
+/// length `1`, the lower bound or the upper bound is generated. If the range has no upper bound,
+/// the limit is used as upper bound. Use [`dice::uni_length`] for uniformly distributed
+/// lengths.
+///
 /// [`Limit`]: crate::Limit
+/// [`dice::usize`]: dice::usize()
+/// [`dice::uni_length`]: dice::uni_length()
 ///
 /// # Panics
 ///
@@ -133,9 +148,52 @@ impl LengthRange for RangeToInclusive<usize> {
 pub fn length(range: impl LengthRange) -> impl Die<usize> {
     let (lower, upper_opt) = range.bounds();
 
+    dice::from_fn(move |fate| {
+        let upper = length_upper_bound(lower, upper_opt, &fate);
+
+        roll_edge_case_or_regular(
+            fate,
+            (lower, upper),
+            &[0, 1],
+            [6, 1, 1],
+            dice::uni_usize(lower..=upper),
+        )
+    })
+}
+
+/// Similar to [`dice::length`], but all lengths are uniformly distributed.
+///
+/// The collection dice always use [`dice::length`]. Collections with uniformly distributed
+/// lengths can be generated by passing a length generated by this die as range.
+///
+/// [`dice::length`]: dice::length()
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let length = fate.with_limit(100.into()).roll(dice::uni_length(42..));
+/// assert!(length >= 42 && length <= 142);
+///
+/// let vec_die = dice::uni_length(..).flat_map(|length| dice::vec(dice::u8(..), length));
+/// let vec = fate.with_limit(100.into()).roll(vec_die);
+/// assert!(vec.len() <= 100);
+/// ```
+pub fn uni_length(range: impl LengthRange) -> impl Die<usize> {
+    let (lower, upper_opt) = range.bounds();
+
     dice::from_fn(move |mut fate| {
-        let upper =
-            upper_opt.unwrap_or_else(|| lower.saturating_add(fate.limit().saturating_to_usize()));
+        let upper = length_upper_bound(lower, upper_opt, &fate);
 
         fate.roll(dice::uni_usize(lower..=upper))
     })
@@ -244,4 +302,56 @@ mod tests {
             );
         })
     }
+
+    fn count_length(fate: &mut Fate, length_die: impl Die<usize>, length: usize) -> usize {
+        (0..2000)
+            .filter(|_| fate.roll(&length_die) == length)
+            .count()
+    }
+
+    #[test]
+    fn length_generates_edge_cases_regularly() {
+        Dicetest::repeatedly().passes(10).run(|mut fate| {
+            let lower = fate.roll(dice::uni_usize(2..=100));
+            let limit = fate.roll(dice::uni_u64(1000..=1_000_000));
+            let upper = lower + limit as usize;
+
+            hint_debug!(lower);
+            hint_debug!(limit);
+
+            let mut fate = fate.with_limit(limit.into());
+
+            // With uniformly distributed lengths each count would be about 2
+            assert!(count_length(&mut fate, dice::length(lower..), lower) >= 20);
+            assert!(count_length(&mut fate, dice::length(lower..), upper) >= 20);
+            assert!(count_length(&mut fate, dice::length(..), 0) >= 20);
+            assert!(count_length(&mut fate, dice::length(..), 1) >= 20);
+        })
+    }
+
+    #[test]
+    fn uni_length_is_in_range_inclusive() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let [a, b] = fate.roll(dice::array(dice::usize(..)));
+            let (lower, upper) = (a.min(b), a.max(b));
+            let length = fate.roll(dice::uni_length(lower..=upper));
+
+            hint_debug!(lower);
+            hint_debug!(upper);
+            hint_debug!(length);
+
+            assert!(lower <= length && length <= upper);
+        })
+    }
+
+    #[test]
+    fn length_calc_stats() {
+        Dicetest::repeatedly()
+            .passes(0)
+            .stats_enabled(true)
+            .run(|mut fate| {
+                stat!("length(..)", "{}", fate.roll(dice::length(..)));
+                stat!("uni_length(..)", "{}", fate.roll(dice::uni_length(..)));
+            })
+    }
 }
@@ -0,0 +1,115 @@
+use crate::prelude::*;
+
+/// A [`Die`] that chooses randomly between the values of a pool.
+///
+/// It's generated by [`dice::pool`]. All values of the pool have the same probability.
+///
+/// [`dice::pool`]: dice::pool()
+#[derive(Debug, Clone)]
+pub struct Pool<T> {
+    values: Vec<T>,
+}
+
+impl<T> Pool<T> {
+    /// Returns the values of the pool.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Clone> DieOnce<T> for Pool<T> {
+    fn roll_once(self, fate: Fate) -> T {
+        self.roll(fate)
+    }
+}
+
+impl<T: Clone> Die<T> for Pool<T> {
+    fn roll(&self, mut fate: Fate) -> T {
+        let index = fate.roll(dice::uni_usize(0..self.values.len()));
+        self.values[index].clone()
+    }
+}
+
+/// Generates a [`Pool`] with `n` values generated by `elem_die`.
+///
+/// The generated [`Pool`] is a [`Die`] that chooses randomly between its values. If a small pool
+/// is used for generating the keys of a map or the elements of a set, then collisions occur
+/// regularly even if `elem_die` rarely generates equal values.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let key_die = fate.roll(dice::pool(5, dice::u64(..)));
+/// let map_die = dice::hash_map(dice::zip().two(&key_die, dice::bool()), 10..);
+///
+/// let map = fate.roll(map_die);
+/// assert!(map.len() <= 5);
+/// assert!(map.keys().all(|key| key_die.values().contains(key)));
+/// ```
+#[track_caller]
+pub fn pool<T>(n: usize, elem_die: impl Die<T>) -> impl Die<Pool<T>> {
+    assert!(n > 0, "The pool must not be empty");
+
+    dice::from_fn(move |mut fate| {
+        let values = (0..n).map(|_| fate.roll(&elem_die)).collect();
+        Pool { values }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn pool_generates_only_values_of_pool() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let n = fate.roll(dice::uni_usize(1..=10));
+            let pool = fate.roll(dice::pool(n, dice::u64(..)));
+            let values = fate.roll(dice::vec(&pool, ..));
+
+            hint_debug!(pool);
+            hint_debug!(values);
+
+            assert_eq!(pool.values().len(), n);
+            assert!(values.iter().all(|value| pool.values().contains(value)));
+        })
+    }
+
+    #[test]
+    fn pool_causes_collisions() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let pool = fate.roll(dice::pool(3, dice::uni_u64(..)));
+            let set = fate.roll(dice::b_tree_set(&pool, 10));
+
+            hint_debug!(pool);
+            hint_debug!(set);
+
+            assert!(set.len() <= 3);
+            assert!(set.is_subset(&pool.values().iter().copied().collect::<BTreeSet<_>>()));
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "The pool must not be empty")]
+    fn pool_panics_if_n_is_zero() {
+        let _ = dice::pool(0, dice::u8(..));
+    }
+
+    #[test]
+    fn pool_fulfills_die_contract() {
+        crate::check_die(dice::pool(5, dice::u8(..)));
+    }
+}
@@ -0,0 +1,319 @@
+use std::cell::{Cell, OnceCell};
+use std::rc::{Rc, Weak};
+
+use crate::Limit;
+use crate::prelude::*;
+
+struct Knot<'a, T> {
+    leaf_die: Box<dyn Die<T> + 'a>,
+    node_die: OnceCell<Box<dyn Die<T> + 'a>>,
+    max_depth: Cell<usize>,
+    // The budget of the current node that is left for its children
+    remaining: Cell<u64>,
+    // The depth of the current node
+    depth: Cell<usize>,
+}
+
+impl<T> Knot<'_, T> {
+    fn roll_node(&self, mut fate: Fate, budget: u64) -> T {
+        let depth = self.depth.get();
+        if budget == 0 || depth >= self.max_depth.get() {
+            return self.leaf_die.roll(fate.with_limit(Limit(budget)));
+        }
+
+        let node_die = self
+            .node_die
+            .get()
+            .expect("The node die of `dice::recursive` was not initialized");
+
+        // The node itself consumes one unit of the budget, hence the recursion terminates
+        let parent_remaining = self.remaining.replace(budget - 1);
+        self.depth.set(depth + 1);
+        let node = node_die.roll(fate.with_limit(Limit(budget)));
+        self.depth.set(depth);
+        self.remaining.set(parent_remaining);
+
+        node
+    }
+}
+
+/// The [`Die`] passed to the closure of [`dice::recursive`].
+///
+/// It generates a child of the current node. Each child gets a random part of the budget the
+/// current node has left. A child with an empty budget is a leaf.
+///
+/// [`dice::recursive`]: dice::recursive()
+pub struct RecursiveInnerDie<'a, T> {
+    knot: Weak<Knot<'a, T>>,
+}
+
+impl<T> Clone for RecursiveInnerDie<'_, T> {
+    fn clone(&self) -> Self {
+        RecursiveInnerDie {
+            knot: self.knot.clone(),
+        }
+    }
+}
+
+impl<T> DieOnce<T> for RecursiveInnerDie<'_, T> {
+    fn roll_once(self, fate: Fate) -> T {
+        self.roll(fate)
+    }
+}
+
+impl<T> Die<T> for RecursiveInnerDie<'_, T> {
+    fn roll(&self, mut fate: Fate) -> T {
+        let knot = self
+            .knot
+            .upgrade()
+            .expect("The inner die of `dice::recursive` was used after its outer die was dropped");
+
+        let remaining = knot.remaining.get();
+        let budget = fate.roll(dice::uni_u64(0..=remaining));
+        knot.remaining.set(remaining - budget);
+
+        knot.roll_node(fate, budget)
+    }
+}
+
+/// The [`Die`] returned by [`dice::recursive`].
+///
+/// [`dice::recursive`]: dice::recursive()
+pub struct RecursiveDie<'a, T> {
+    knot: Rc<Knot<'a, T>>,
+}
+
+impl<T> RecursiveDie<'_, T> {
+    /// Limits the depth of the generated values.
+    ///
+    /// Nodes at depth `max_depth` are always leaves. A small maximal depth distributes the budget
+    /// to more nodes near the root and hence results in broad values, whereas a large maximal
+    /// depth allows deep values. By default the depth is only limited by the budget.
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        self.knot.max_depth.set(max_depth);
+        self
+    }
+}
+
+impl<T> DieOnce<T> for RecursiveDie<'_, T> {
+    fn roll_once(self, fate: Fate) -> T {
+        self.roll(fate)
+    }
+}
+
+impl<T> Die<T> for RecursiveDie<'_, T> {
+    fn roll(&self, mut fate: Fate) -> T {
+        let budget = fate.roll(dice::uni_u64(0..=fate.limit().0));
+        self.knot.remaining.set(0);
+        self.knot.depth.set(0);
+        self.knot.roll_node(fate, budget)
+    }
+}
+
+/// Generates values of a recursive type, e.g. trees, ASTs or linked lists.
+///
+/// The closure `node_die` gets a [`Die`] for generating the recursive children and must return
+/// a [`Die`] for a single node. The inner [`Die`] can be cloned if a node has children of
+/// different kinds. The `leaf_die` generates values without children.
+///
+/// The [`Limit`] is used as a budget for the overall number of nodes. The root gets a random
+/// budget between zero and the limit. Each node consumes one unit of its budget and its
+/// children get random parts of the rest in the style of [`dice::split_limit_n`]. A node with an
+/// empty budget is generated by `leaf_die`. Hence the generation always terminates and the
+/// number of nodes that are not leaves is bounded by the limit. Use [`RecursiveDie::max_depth`]
+/// for controlling the depth versus the breadth of the values.
+///
+/// The dice `leaf_die` and `node_die` are rolled with the budget of their node as limit.
+///
+/// [`dice::split_limit_n`]: dice::split_limit_n()
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// #[derive(Debug, Clone)]
+/// enum Json {
+///     Null,
+///     Number(u8),
+///     Array(Vec<Json>),
+/// }
+///
+/// impl Json {
+///     fn array_count(&self) -> u64 {
+///         match self {
+///             Json::Null | Json::Number(_) => 0,
+///             Json::Array(elems) => 1 + elems.iter().map(Json::array_count).sum::<u64>(),
+///         }
+///     }
+/// }
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let leaf_die = dice::one_of_die().two(
+///     dice::just(Json::Null),
+///     dice::u8(..).map(Json::Number),
+/// );
+/// let json_die = dice::recursive(leaf_die, |inner_die| {
+///     dice::vec(inner_die, ..).map(Json::Array)
+/// });
+///
+/// for _ in 0..10 {
+///     let json = fate.roll(&json_die);
+///     assert!(json.array_count() <= limit.0);
+/// }
+/// ```
+///
+/// The depth can be limited:
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// #[derive(Debug, Clone)]
+/// enum Tree {
+///     Leaf,
+///     Node(Box<Tree>, Box<Tree>),
+/// }
+///
+/// fn depth(tree: &Tree) -> usize {
+///     match tree {
+///         Tree::Leaf => 0,
+///         Tree::Node(left, right) => 1 + depth(left).max(depth(right)),
+///     }
+/// }
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let tree_die = dice::recursive(dice::just(Tree::Leaf), |inner_die| {
+///     dice::zip()
+///         .two(inner_die.clone(), inner_die)
+///         .map(|(left, right)| Tree::Node(Box::new(left), Box::new(right)))
+/// })
+/// .max_depth(3);
+///
+/// let tree = fate.roll(tree_die);
+/// assert!(depth(&tree) <= 3);
+/// ```
+pub fn recursive<'a, T, LD, ND>(
+    leaf_die: LD,
+    node_die: impl FnOnce(RecursiveInnerDie<'a, T>) -> ND,
+) -> RecursiveDie<'a, T>
+where
+    T: 'a,
+    LD: Die<T> + 'a,
+    ND: Die<T> + 'a,
+{
+    let knot = Rc::new(Knot {
+        leaf_die: Box::new(leaf_die),
+        node_die: OnceCell::new(),
+        max_depth: Cell::new(usize::MAX),
+        remaining: Cell::new(0),
+        depth: Cell::new(0),
+    });
+
+    let inner_die = RecursiveInnerDie {
+        knot: Rc::downgrade(&knot),
+    };
+    let node_die: Box<dyn Die<T> + 'a> = Box::new(node_die(inner_die));
+    let _ = knot.node_die.set(node_die);
+
+    RecursiveDie { knot }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dice::RecursiveDie;
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone)]
+    enum Tree {
+        Leaf,
+        Node(Vec<Tree>),
+    }
+
+    impl Tree {
+        fn node_count(&self) -> u64 {
+            match self {
+                Tree::Leaf => 0,
+                Tree::Node(children) => 1 + children.iter().map(Tree::node_count).sum::<u64>(),
+            }
+        }
+
+        fn depth(&self) -> usize {
+            match self {
+                Tree::Leaf => 0,
+                Tree::Node(children) => 1 + children.iter().map(Tree::depth).max().unwrap_or(0),
+            }
+        }
+    }
+
+    fn tree_die() -> RecursiveDie<'static, Tree> {
+        dice::recursive(dice::just(Tree::Leaf), |inner_die| {
+            dice::vec(inner_die, ..).map(Tree::Node)
+        })
+    }
+
+    #[test]
+    fn node_count_is_bounded_by_limit() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let limit = fate.roll(dice::u64(..=1000));
+            let tree = fate.with_limit(limit.into()).roll(tree_die());
+            let node_count = tree.node_count();
+
+            hint_debug!(limit);
+            hint_debug!(node_count);
+
+            assert!(node_count <= limit);
+        })
+    }
+
+    #[test]
+    fn depth_is_bounded_by_max_depth() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let max_depth = fate.roll(dice::uni_usize(0..=5));
+            let tree = fate.roll(tree_die().max_depth(max_depth));
+            let depth = tree.depth();
+
+            hint_debug!(max_depth);
+            hint_debug!(depth);
+
+            assert!(depth <= max_depth);
+        })
+    }
+
+    #[test]
+    fn terminates_for_unconditional_recursion() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let die = dice::recursive(dice::just(Tree::Leaf), |inner_die| {
+                dice::zip()
+                    .two(inner_die.clone(), inner_die)
+                    .map(|(left, right)| Tree::Node(vec![left, right]))
+            });
+            let tree = fate.with_limit(10_000.into()).roll(die);
+            assert!(tree.node_count() <= 10_000);
+        })
+    }
+
+    #[test]
+    fn fulfills_die_contract() {
+        crate::check_die(tree_die());
+    }
+
+    #[test]
+    fn recursive_calc_stats() {
+        Dicetest::repeatedly()
+            .passes(0)
+            .stats_enabled(true)
+            .run(|mut fate| {
+                let tree = fate.roll(tree_die());
+                stat!("node count", "{}", tree.node_count());
+                stat!("depth", "{}", tree.depth());
+            })
+    }
+}
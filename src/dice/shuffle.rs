@@ -58,6 +58,7 @@ pub fn shuffled_vec<T>(mut vec: Vec<T>) -> impl DieOnce<Vec<T>> {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
+    use crate::statistics;
     use std::collections::HashMap;
 
     fn count_vec_elems(vec: &[u8]) -> HashMap<u8, usize> {
@@ -84,6 +85,19 @@ mod tests {
         })
     }
 
+    #[test]
+    fn shuffled_vec_is_uniform() {
+        Dicetest::repeatedly().passes(10).run(|fate| {
+            let die = dice::from_fn(|mut fate| fate.roll(dice::shuffled_vec(vec![0, 1, 2, 3, 4])));
+            let expected = [1.0 / 5.0; 5];
+            let p_value = statistics::chi_squared(fate, die, 10_000, &expected, |vec| {
+                vec.iter().position(|&elem| elem == 0).unwrap()
+            });
+            hint_debug!(p_value);
+            assert!(p_value > 1e-6);
+        })
+    }
+
     #[test]
     fn shuffled_vec_calc_stats() {
         Dicetest::repeatedly()
@@ -0,0 +1,380 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+use crate::{Prng, Seed};
+
+// Returns a pseudorandom number for the given seed. Equal seeds result in equal numbers.
+fn salted_number(prng: &Prng, seed: Seed) -> u64 {
+    let mut prng = prng.clone();
+    prng.reseed(seed);
+    prng.next_number()
+}
+
+/// The value generated by [`dice::total_order`].
+///
+/// [`dice::total_order`]: dice::total_order()
+pub struct TotalOrder<T, TC> {
+    codie: TC,
+    prng: Prng,
+    _t: PhantomData<fn(T)>,
+}
+
+impl<T, TC> TotalOrder<T, TC>
+where
+    T: Ord + Clone,
+    TC: Codie<T>,
+{
+    /// Compares the given values with respect to the generated order.
+    pub fn cmp(&self, left: &T, right: &T) -> Ordering {
+        let key = |value: &T| salted_number(&self.prng, self.codie.coroll(value.clone()));
+        key(left).cmp(&key(right)).then_with(|| left.cmp(right))
+    }
+
+    /// Converts the order into a function that can be passed to functions like
+    /// [`slice::sort_by`].
+    pub fn build_fn(self) -> impl Fn(&T, &T) -> Ordering {
+        move |left, right| self.cmp(left, right)
+    }
+}
+
+/// Generates a random total order for `T`.
+///
+/// The order is consistent with the [`Eq`] implementation of `T`, i.e. two values are
+/// [`Ordering::Equal`] iff they are equal. This requires that the given [`Codie`] returns
+/// equal seeds for equal values.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let order = fate.roll(dice::total_order(codice::stable::<u8>()));
+///
+/// let mut values = vec![3, 1, 2, 1];
+/// values.sort_by(order.build_fn());
+/// assert_eq!(values.len(), 4);
+/// ```
+pub fn total_order<T, TC>(codie: TC) -> impl DieOnce<TotalOrder<T, TC>>
+where
+    T: Ord + Clone,
+    TC: Codie<T>,
+{
+    dice::from_fn_once(|mut fate| TotalOrder {
+        codie,
+        prng: fate.fork_prng(),
+        _t: PhantomData,
+    })
+}
+
+/// The value generated by [`dice::equivalence_relation`].
+///
+/// [`dice::equivalence_relation`]: dice::equivalence_relation()
+pub struct EquivalenceRelation<T, TC> {
+    codie: TC,
+    prng: Prng,
+    class_count: u64,
+    _t: PhantomData<fn(T)>,
+}
+
+impl<T, TC> EquivalenceRelation<T, TC>
+where
+    T: Clone,
+    TC: Codie<T>,
+{
+    /// Returns the equivalence class of the given value. The classes are numbered from zero to
+    /// [`EquivalenceRelation::class_count`] (exclusive).
+    pub fn class(&self, value: &T) -> u64 {
+        salted_number(&self.prng, self.codie.coroll(value.clone())) % self.class_count
+    }
+
+    /// Returns the number of equivalence classes. Some of them might be empty.
+    pub fn class_count(&self) -> u64 {
+        self.class_count
+    }
+
+    /// Returns whether the given values are equivalent.
+    pub fn equiv(&self, left: &T, right: &T) -> bool {
+        self.class(left) == self.class(right)
+    }
+
+    /// Converts the relation into a function that can be passed to functions like
+    /// [`Vec::dedup_by`].
+    pub fn build_fn(self) -> impl Fn(&T, &T) -> bool {
+        move |left, right| self.equiv(left, right)
+    }
+}
+
+/// Generates a random equivalence relation for `T`.
+///
+/// The relation is reflexive, symmetric and transitive. Equal values are equivalent if the
+/// given [`Codie`] returns equal seeds for equal values. The number of equivalence classes
+/// is at least one and depends on the [`Limit`], hence a small limit results in many
+/// equivalent values.
+///
+/// [`Limit`]: crate::Limit
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let relation = fate.roll(dice::equivalence_relation(codice::stable::<u8>()));
+///
+/// assert!(relation.equiv(&42, &42));
+/// assert_eq!(relation.equiv(&1, &2), relation.equiv(&2, &1));
+/// ```
+pub fn equivalence_relation<T, TC>(codie: TC) -> impl DieOnce<EquivalenceRelation<T, TC>>
+where
+    T: Clone,
+    TC: Codie<T>,
+{
+    dice::from_fn_once(|mut fate| EquivalenceRelation {
+        codie,
+        class_count: fate.roll(dice::length(1..)) as u64,
+        prng: fate.fork_prng(),
+        _t: PhantomData,
+    })
+}
+
+/// The value generated by [`dice::monotone_fn`].
+///
+/// [`dice::monotone_fn`]: dice::monotone_fn()
+#[derive(Debug, Clone)]
+pub struct MonotoneFn<T> {
+    // Sorted points where the output increases.
+    breakpoints: Vec<T>,
+    // The output for the inputs between two breakpoints. It has one more element than
+    // `breakpoints` and is non-decreasing.
+    outputs: Vec<u64>,
+}
+
+impl<T: Ord> MonotoneFn<T> {
+    /// Applies the function to the given value.
+    pub fn apply(&self, value: &T) -> u64 {
+        let index = self
+            .breakpoints
+            .partition_point(|breakpoint| breakpoint <= value);
+        self.outputs[index]
+    }
+
+    /// Converts the monotone function into a closure.
+    pub fn build_fn(self) -> impl Fn(&T) -> u64 {
+        move |value| self.apply(value)
+    }
+}
+
+/// Generates a random non-decreasing function from `T` to [`u64`].
+///
+/// The function is a step function whose steps are located at values generated by the given
+/// [`Die`]. For all `x <= y` it holds `f(x) <= f(y)`. Some steps might have a height of zero,
+/// hence different values might have the same output.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let f = fate.roll(dice::monotone_fn(dice::i32(..)));
+///
+/// assert!(f.apply(&-100) <= f.apply(&100));
+/// ```
+pub fn monotone_fn<T, D>(breakpoint_die: D) -> impl Die<MonotoneFn<T>>
+where
+    T: Ord,
+    D: Die<T>,
+{
+    let steps_die = dice::vec(dice::zip().two(breakpoint_die, dice::u32(..)), ..);
+
+    dice::from_fn(move |mut fate| {
+        let mut steps = fate.roll(&steps_die);
+        steps.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let mut output = u64::from(fate.roll(dice::u32(..)));
+        let mut breakpoints = Vec::with_capacity(steps.len());
+        let mut outputs = Vec::with_capacity(steps.len() + 1);
+        outputs.push(output);
+
+        for (breakpoint, height) in steps {
+            output = output.saturating_add(u64::from(height));
+            // Equal breakpoints are merged, otherwise the step would be invisible
+            match breakpoints.last() {
+                Some(last) if *last == breakpoint => {
+                    *outputs.last_mut().unwrap() = output;
+                }
+                _ => {
+                    breakpoints.push(breakpoint);
+                    outputs.push(output);
+                }
+            }
+        }
+
+        MonotoneFn {
+            breakpoints,
+            outputs,
+        }
+    })
+}
+
+/// The value generated by [`dice::hash_fn`].
+///
+/// [`dice::hash_fn`]: dice::hash_fn()
+pub struct HashFn<T, TC> {
+    codie: TC,
+    prng: Prng,
+    mask: u64,
+    _t: PhantomData<fn(T)>,
+}
+
+impl<T, TC> HashFn<T, TC>
+where
+    T: Clone,
+    TC: Codie<T>,
+{
+    /// Returns the hash of the given value.
+    pub fn hash(&self, value: &T) -> u64 {
+        salted_number(&self.prng, self.codie.coroll(value.clone())) & self.mask
+    }
+
+    /// Converts the hash function into a closure.
+    pub fn build_fn(self) -> impl Fn(&T) -> u64 {
+        move |value| self.hash(value)
+    }
+}
+
+/// Generates a random hash function for `T`.
+///
+/// The hash function is consistent with the [`Eq`] implementation of `T`, i.e. equal values
+/// have equal hashes. This requires that the given [`Codie`] returns equal seeds for equal
+/// values. Once in a while the hashes use only a few bits, hence collisions of unequal values
+/// are likely.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let hash_fn = fate.roll(dice::hash_fn(codice::stable::<String>()));
+///
+/// assert_eq!(hash_fn.hash(&"foo".to_string()), hash_fn.hash(&"foo".to_string()));
+/// ```
+pub fn hash_fn<T, TC>(codie: TC) -> impl DieOnce<HashFn<T, TC>>
+where
+    T: Clone,
+    TC: Codie<T>,
+{
+    dice::from_fn_once(|mut fate| {
+        let bits = fate
+            .roll(dice::weighted_one_of_die().two((6, dice::just(64)), (2, dice::uni_u32(0..=8))));
+        let mask = u64::MAX.checked_shr(64 - bits).unwrap_or(0);
+        HashFn {
+            codie,
+            prng: fate.fork_prng(),
+            mask,
+            _t: PhantomData,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn total_order_is_consistent_with_eq() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let order = fate.roll(dice::total_order(codice::stable::<u8>()));
+            let [x, y] = fate.roll(dice::array(dice::u8(..)));
+
+            assert_eq!(order.cmp(&x, &y) == Ordering::Equal, x == y);
+            assert_eq!(order.cmp(&x, &y), order.cmp(&y, &x).reverse());
+        })
+    }
+
+    #[test]
+    fn total_order_is_transitive() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let order = fate.roll(dice::total_order(codice::stable::<u8>()));
+            let mut values: [u8; 3] = fate.roll(dice::array(dice::u8(..)));
+            values.sort_by(|x, y| order.cmp(x, y));
+            let [x, y, z] = values;
+
+            assert_ne!(order.cmp(&x, &y), Ordering::Greater);
+            assert_ne!(order.cmp(&y, &z), Ordering::Greater);
+            assert_ne!(order.cmp(&x, &z), Ordering::Greater);
+        })
+    }
+
+    #[test]
+    fn equivalence_relation_is_symmetric_and_transitive() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let relation = fate.roll(dice::equivalence_relation(codice::stable::<u8>()));
+            let [x, y, z] = fate.roll(dice::array(dice::u8(..)));
+
+            assert!(relation.equiv(&x, &x));
+            assert_eq!(relation.equiv(&x, &y), relation.equiv(&y, &x));
+            if relation.equiv(&x, &y) && relation.equiv(&y, &z) {
+                assert!(relation.equiv(&x, &z));
+            }
+            assert!(relation.class(&x) < relation.class_count());
+        })
+    }
+
+    #[test]
+    fn monotone_fn_is_monotone() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let f = fate.roll(dice::monotone_fn(dice::i8(..)));
+            let [x, y] = fate.roll(dice::array(dice::i8(..)));
+            let (x, y) = (x.min(y), x.max(y));
+
+            assert!(f.apply(&x) <= f.apply(&y));
+        })
+    }
+
+    #[test]
+    fn hash_fn_is_consistent_with_eq() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let hash_fn = fate.roll(dice::hash_fn(codice::stable::<String>()));
+            let value = fate.roll(dice::string(dice::char(), ..));
+
+            assert_eq!(hash_fn.hash(&value), hash_fn.hash(&value.clone()));
+        })
+    }
+
+    #[test]
+    fn equivalence_relation_calc_stats() {
+        Dicetest::repeatedly()
+            .passes(0)
+            .stats_enabled(true)
+            .run(|mut fate| {
+                let relation = fate.roll(dice::equivalence_relation(codice::stable::<u8>()));
+                stat!(
+                    "dice::equivalence_relation(..).equiv(&0, &1)",
+                    "{}",
+                    relation.equiv(&0, &1),
+                );
+            })
+    }
+}
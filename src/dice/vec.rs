@@ -91,10 +91,108 @@ pub fn outer_vec<T>(elem_die: impl Die<T>, length_range: impl LengthRange) -> im
     dice::outer_collection(VecBuilder::die(), elem_die, length_range)
 }
 
+/// Similar to [`dice::vec`] but elements are deliberately repeated.
+///
+/// Each element except the first one is with probability `duplicate_ratio` a clone of a randomly
+/// chosen previous element, otherwise it's generated with `elem_die`. Hence duplicates occur
+/// regularly even if `elem_die` rarely generates equal values, which helps to test sorting,
+/// deduplication, multisets, etc.
+///
+/// [`dice::vec`]: dice::vec()
+///
+/// # Panics
+///
+/// Panics if the range is empty or if `duplicate_ratio` is not inside the closed interval
+/// `[0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Prng, Limit};
+///
+/// let mut prng = Prng::from_seed(0x5EED.into());
+/// let limit = Limit::default();
+/// let mut fate = Fate::new(&mut prng, limit);
+///
+/// let vec_die = dice::vec_with_duplicates(dice::u64(..), 10..=20, 0.5);
+/// let vec = fate.roll(vec_die);
+/// assert!(vec.len() >= 10 && vec.len() <= 20);
+///
+/// // All elements are clones of the first one
+/// let vec_die = dice::vec_with_duplicates(dice::u64(..), 10..=20, 1.0);
+/// let vec = fate.roll(vec_die);
+/// assert!(vec.iter().all(|&elem| elem == vec[0]));
+/// ```
+#[track_caller]
+pub fn vec_with_duplicates<T>(
+    elem_die: impl Die<T>,
+    length_range: impl LengthRange,
+    duplicate_ratio: f64,
+) -> impl Die<Vec<T>>
+where
+    T: Clone,
+{
+    assert!(
+        (0.0..=1.0).contains(&duplicate_ratio),
+        "The duplicate ratio must be inside the interval [0, 1], but is {duplicate_ratio}"
+    );
+
+    let length_die = dice::length(length_range);
+    dice::from_fn(move |mut fate| {
+        let length = fate.roll(&length_die);
+        let mut vec: Vec<T> = Vec::with_capacity(length);
+        for _ in 0..length {
+            let is_duplicate =
+                !vec.is_empty() && fate.roll(dice::open_unit_f64()) < duplicate_ratio;
+            let elem = if is_duplicate {
+                let index = fate.roll(dice::uni_usize(0..vec.len()));
+                vec[index].clone()
+            } else {
+                fate.roll(&elem_die)
+            };
+            vec.push(elem);
+        }
+        vec
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use crate::prelude::*;
 
+    #[test]
+    fn vec_with_duplicates_has_only_duplicates_if_ratio_is_one() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let vec = fate.roll(dice::vec_with_duplicates(dice::u64(..), .., 1.0));
+            hint_debug!(vec);
+
+            assert!(vec.windows(2).all(|window| window[0] == window[1]));
+        })
+    }
+
+    #[test]
+    fn vec_with_duplicates_has_duplicates_regularly() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let vec = fate.roll(dice::vec_with_duplicates(dice::uni_u64(..), 40..=100, 0.5));
+            let length = vec.len();
+            let distinct_count = vec.iter().collect::<HashSet<_>>().len();
+
+            hint_debug!(length);
+            hint_debug!(distinct_count);
+
+            // The expected number of duplicates is about half the length
+            assert!(distinct_count <= length * 9 / 10);
+        })
+    }
+
+    #[test]
+    fn vec_with_duplicates_fulfills_die_contract() {
+        crate::check_die(dice::vec_with_duplicates(dice::u8(..), .., 0.3));
+    }
+
     #[test]
     fn vec_calc_stats() {
         Dicetest::repeatedly()
@@ -8,8 +8,11 @@
 ///
 /// # Recursion
 ///
-/// Deriving [`Dice`](./trait.Dice.html) for recursive types isn't supported for now.
-/// You need to implement it manually.
+/// Deriving [`Dice`](./trait.Dice.html) for recursive types isn't supported. A derive macro
+/// only sees a single type, hence it can't detect mutual recursion and can't guarantee that the
+/// generation terminates. You need to implement it manually, e.g. with
+/// [`dice::recursive`](crate::dice::recursive()), which chooses the non-recursive cases as
+/// soon as the [`Limit`](crate::Limit) is exhausted.
 ///
 /// ```rust
 /// // Recursive types like Foo are not supported!
@@ -46,6 +49,27 @@
 /// }
 /// ```
 ///
+/// There are no dedicated attributes for ranges, lengths or default values because `die`
+/// already covers them:
+///
+/// ```rust
+/// use dicetest::prelude::*;
+///
+/// #[derive(Dice)]
+/// struct Config {
+///     #[dice(die = dice::u8(1..=10))]
+///     retries: u8,
+///     #[dice(die = dice::vec(dice::u8(..), 1..=5))]
+///     payload: Vec<u8>,
+///     #[dice(die = dice::from_fn(|_| Default::default()))]
+///     name: String,
+/// }
+/// ```
+///
+/// Weighting the split of the [`Limit`](crate::Limit), custom generic bounds and filters on the
+/// whole value aren't supported. Implement [`Dice`](./trait.Dice.html) manually or use
+/// [`Die::filter`](crate::Die::filter) on the derived die instead.
+///
 /// ## weight
 ///
 /// The attribute `weight` can be used on a variant to define the probability of this variant
@@ -1,5 +1,8 @@
-use crate::adapters::{ArcDie, BoxedDie, FlatMapDie, FlattenDie, MapDie, RcDie};
-use crate::{DieOnce, Fate};
+use crate::adapters::{
+    ArcDie, BoxedDie, FilterDie, FilterMapDie, FlatMapDie, FlattenDie, MapDie, MapLimitDie, RcDie,
+    ScaleLimitDie, TryFilterDie, TryFilterMapDie, WithLimitDie,
+};
+use crate::{DieOnce, Fate, Limit, Prng, Seed};
 
 /// Trait for generating pseudorandom values of type `T`.
 ///
@@ -8,8 +11,11 @@ use crate::{DieOnce, Fate};
 /// For example an implementation of [`Die`] must produce the same value with its methods
 /// [`roll`] and [`roll_once`] if they are called with the same [`Fate`].
 ///
+/// The function [`check_die`] can be used for checking these rules.
+///
 /// [`roll_once`]: DieOnce::roll_once
 /// [`roll`]: Die::roll
+/// [`check_die`]: crate::check_die
 pub trait Die<T>: DieOnce<T> {
     /// Generates a pseudorandom value.
     ///
@@ -55,6 +61,263 @@ pub trait Die<T>: DieOnce<T> {
         FlatMapDie::new(self, f)
     }
 
+    /// Creates a new [`Die`] that only generates values of `self` that satisfy the predicate.
+    ///
+    /// The values are generated repeatedly with new randomness from the same [`Fate`] until the
+    /// predicate `f` returns `true`. The number of rejected values is recorded as stat. Use
+    /// [`FilterDie::max_tries`] for changing the maximum number of tries.
+    ///
+    /// The filter should reject only few values, otherwise the generation becomes slow.
+    /// Consider generating valid values directly instead.
+    ///
+    /// There is no counterpart for [`DieOnce`] because a [`DieOnce`] generates only a single
+    /// value and hence can't generate another one after a rejection. Use [`Die::try_filter`]
+    /// if the test run should be discarded instead of failing when the tries are exhausted.
+    ///
+    /// # Panics
+    ///
+    /// The new [`Die`] panics if the predicate rejected the maximum number of tries in a row.
+    /// The panic message contains the label set with [`FilterDie::label`] or, if there is none,
+    /// the type name of the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let even_die = dice::u8(..).filter(|x| x % 2 == 0);
+    /// assert!(fate.roll(even_die) % 2 == 0);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// // Oh no, panic!
+    /// let _ = fate.roll(dice::bool().filter(|_| false));
+    /// ```
+    fn filter<F>(self, f: F) -> FilterDie<T, Self, F>
+    where
+        Self: Sized,
+        F: Fn(&T) -> bool,
+    {
+        FilterDie::new(self, f)
+    }
+
+    /// Creates a new [`Die`] that maps the generated values of `self` and only generates
+    /// the results that are `Some`.
+    ///
+    /// The values are generated repeatedly with new randomness from the same [`Fate`] until the
+    /// function `f` returns `Some`. See [`filter`] for more details.
+    ///
+    /// [`filter`]: Die::filter
+    ///
+    /// # Panics
+    ///
+    /// The new [`Die`] panics if the function rejected the maximum number of tries in a row.
+    /// The panic message contains the label set with [`FilterMapDie::label`] or, if there is
+    /// none, the type name of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let digit_die = dice::u8(b'0'..=b'z')
+    ///     .filter_map(|byte| char::from(byte).to_digit(10))
+    ///     .max_tries(1000);
+    /// assert!(fate.roll(digit_die) < 10);
+    /// ```
+    fn filter_map<U, F>(self, f: F) -> FilterMapDie<T, U, Self, F>
+    where
+        Self: Sized,
+        F: Fn(T) -> Option<U>,
+    {
+        FilterMapDie::new(self, f)
+    }
+
+    /// Similar to [`filter`], but generates `None` instead of panicking if the predicate
+    /// rejected the maximum number of tries in a row.
+    ///
+    /// This allows to discard a test run whose test data can't be generated, e.g. by returning
+    /// early from the test.
+    ///
+    /// [`filter`]: Die::filter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let even_die = dice::u8(..).try_filter(|x| x % 2 == 0);
+    /// if let Some(even) = fate.roll(even_die) {
+    ///     assert!(even % 2 == 0);
+    /// }
+    ///
+    /// let never_die = dice::bool().try_filter(|_| false);
+    /// assert_eq!(fate.roll(never_die), None);
+    /// ```
+    fn try_filter<F>(self, f: F) -> TryFilterDie<T, Self, F>
+    where
+        Self: Sized,
+        F: Fn(&T) -> bool,
+    {
+        TryFilterDie::new(self, f)
+    }
+
+    /// Similar to [`filter_map`], but generates `None` instead of panicking if the function
+    /// rejected the maximum number of tries in a row.
+    ///
+    /// [`filter_map`]: Die::filter_map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let digit_die = dice::u8(b'0'..=b'z')
+    ///     .try_filter_map(|byte| char::from(byte).to_digit(10));
+    /// if let Some(digit) = fate.roll(digit_die) {
+    ///     assert!(digit < 10);
+    /// }
+    /// ```
+    fn try_filter_map<U, F>(self, f: F) -> TryFilterMapDie<T, U, Self, F>
+    where
+        Self: Sized,
+        F: Fn(T) -> Option<U>,
+    {
+        TryFilterMapDie::new(self, f)
+    }
+
+    /// Creates a new [`Die`] that always uses the given [`Limit`] for `self` and ignores the
+    /// [`Limit`] of the [`Fate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit::default();
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// // Each inner vec has at most 3 elements
+    /// let vec_die = dice::vec(dice::vec(dice::u8(..), ..).with_limit(Limit(3)), ..);
+    /// let vec = fate.roll(vec_die);
+    /// assert!(vec.iter().all(|inner| inner.len() <= 3));
+    /// ```
+    fn with_limit(self, limit: Limit) -> WithLimitDie<T, Self>
+    where
+        Self: Sized,
+    {
+        WithLimitDie::new(self, limit)
+    }
+
+    /// Creates a new [`Die`] that uses the [`Limit`] of the [`Fate`] multiplied with `factor`
+    /// for `self`.
+    ///
+    /// The scaled [`Limit`] is calculated with [`f64`] precision, rounded down and saturates at
+    /// the largest [`u64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is negative or NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit(100);
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let vec = fate.roll(dice::vec(dice::u8(..), ..).scale_limit(0.1));
+    /// assert!(vec.len() <= 10);
+    /// ```
+    #[track_caller]
+    fn scale_limit(self, factor: f64) -> ScaleLimitDie<T, Self>
+    where
+        Self: Sized,
+    {
+        ScaleLimitDie::new(self, factor)
+    }
+
+    /// Creates a new [`Die`] that uses the [`Limit`] of the [`Fate`] mapped with `f` for `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit(100);
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// // The length grows logarithmically with the limit
+    /// let die = dice::vec(dice::u8(..), ..).map_limit(|limit| Limit((limit.0 + 1).ilog2().into()));
+    /// assert!(fate.roll(die).len() <= 6);
+    /// ```
+    fn map_limit<F>(self, f: F) -> MapLimitDie<T, Self, F>
+    where
+        Self: Sized,
+        F: Fn(Limit) -> Limit,
+    {
+        MapLimitDie::new(self, f)
+    }
+
+    /// Creates a new [`Die`] that always uses [`Limit::default`] for `self`.
+    ///
+    /// The distribution of the generated values doesn't depend on the [`Limit`] of the
+    /// [`Fate`]. This is useful for small values that should look the same in every test run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::{Prng, Limit};
+    ///
+    /// let mut prng = Prng::from_seed(0x5EED.into());
+    /// let limit = Limit(0);
+    /// let mut fate = Fate::new(&mut prng, limit);
+    ///
+    /// let name_die = dice::string(dice::char(), 1..).limit_independent();
+    /// assert!(!fate.roll(name_die).is_empty());
+    /// ```
+    fn limit_independent(self) -> WithLimitDie<T, Self>
+    where
+        Self: Sized,
+    {
+        WithLimitDie::new(self, Limit::default())
+    }
+
     /// Puts `self` behind a [`Box`] pointer.
     fn boxed<'a>(self) -> BoxedDie<'a, T>
     where
@@ -78,6 +341,30 @@ pub trait Die<T>: DieOnce<T> {
     {
         ArcDie::new(self)
     }
+
+    /// Returns an infinite iterator of values generated by `self`.
+    ///
+    /// The values are generated with a [`Prng`] created from the given [`Seed`] and the given
+    /// [`Limit`]. This is useful for previewing the values of a generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicetest::prelude::*;
+    /// use dicetest::Limit;
+    ///
+    /// let die = dice::vec(dice::u8(..), ..);
+    /// for vec in die.samples(0x5EED.into(), Limit(5)).take(3) {
+    ///     assert!(vec.len() <= 5);
+    /// }
+    /// ```
+    fn samples(&self, seed: Seed, limit: Limit) -> impl Iterator<Item = T> + '_
+    where
+        Self: Sized,
+    {
+        let mut prng = Prng::from_seed(seed);
+        std::iter::repeat_with(move || self.roll(Fate::new(&mut prng, limit)))
+    }
 }
 
 impl<T, TD: Die<T>> DieOnce<T> for &TD {
@@ -91,3 +378,179 @@ impl<T, TD: Die<T>> Die<T> for &TD {
         (**self).roll(fate)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{Limit, Prng, Seed};
+
+    #[test]
+    fn samples_are_deterministic_and_respect_limit() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let seed = Seed(fate.roll(dice::u64(..)));
+            let limit = Limit(fate.roll(dice::uni_u64(0..=20)));
+            let die = dice::vec(dice::u8(..), ..);
+
+            let samples = die.samples(seed, limit).take(5).collect::<Vec<_>>();
+            let other_samples = die.samples(seed, limit).take(5).collect::<Vec<_>>();
+
+            assert_eq!(samples, other_samples);
+            assert!(samples.iter().all(|vec| vec.len() as u64 <= limit.0));
+
+            let mut prng = Prng::from_seed(seed);
+            assert_eq!(samples[0], Fate::new(&mut prng, limit).roll(&die));
+        })
+    }
+
+    #[test]
+    fn filter_generates_only_accepted_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let divisor = fate.roll(dice::u8(1..=5));
+            let value = fate.roll(dice::u8(..).filter(|x| x % divisor == 0));
+
+            hint_debug!(divisor);
+            hint_debug!(value);
+
+            assert_eq!(value % divisor, 0);
+        })
+    }
+
+    #[test]
+    fn filter_map_generates_only_mapped_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::i8(..).filter_map(|x| u8::try_from(x).ok()));
+            assert!(value <= 127);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "rejected 7 values in a row")]
+    fn filter_panics_if_tries_are_exhausted() {
+        let mut prng = Prng::from_seed(Seed(42));
+        let die = dice::bool().filter(|_| false).max_tries(7);
+        Fate::new(&mut prng, Limit(0)).roll(die);
+    }
+
+    #[test]
+    #[should_panic(expected = "The filter `always false` rejected 7 values in a row")]
+    fn filter_panics_with_label() {
+        let mut prng = Prng::from_seed(Seed(42));
+        let die = dice::bool()
+            .filter(|_| false)
+            .max_tries(7)
+            .label("always false");
+        Fate::new(&mut prng, Limit(0)).roll(die);
+    }
+
+    #[test]
+    #[should_panic(expected = "The filter `never some` rejected 100 values in a row")]
+    fn filter_map_panics_with_label() {
+        let mut prng = Prng::from_seed(Seed(42));
+        let die = dice::bool().filter_map(|_| None::<u8>).label("never some");
+        Fate::new(&mut prng, Limit(0)).roll(die);
+    }
+
+    #[test]
+    fn try_filter_generates_none_if_tries_are_exhausted() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let max_tries = fate.roll(dice::u32(1..=10));
+            hint_debug!(max_tries);
+
+            let die = dice::bool().try_filter(|_| false).max_tries(max_tries);
+            assert_eq!(fate.roll(die), None);
+
+            let die = dice::bool()
+                .try_filter_map(|_| None::<u8>)
+                .max_tries(max_tries);
+            assert_eq!(fate.roll(die), None);
+        })
+    }
+
+    #[test]
+    fn try_filter_generates_only_accepted_values() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let value = fate.roll(dice::u8(..).try_filter(|x| x % 2 == 0));
+            assert!(value.is_none_or(|value| value % 2 == 0));
+
+            let value = fate.roll(dice::i8(..).try_filter_map(|x| u8::try_from(x).ok()));
+            assert!(value.is_none_or(|value| value <= 127));
+        })
+    }
+
+    #[test]
+    fn try_filter_fulfills_die_contract() {
+        crate::check_die(dice::u8(..).try_filter(|x| x % 3 == 0));
+        crate::check_die(dice::u64(..).try_filter_map(|x| x.checked_mul(3)));
+    }
+
+    #[test]
+    fn filter_records_rejections() {
+        if cfg!(feature = "stats") {
+            let mut prng = Prng::from_seed(Seed(42));
+            let die = dice::u8(..).filter(|x| x % 2 == 0);
+            let ((), stats) = crate::stats::collect(|| {
+                for _ in 0..100 {
+                    Fate::new(&mut prng, Limit(0)).roll(&die);
+                }
+            });
+
+            let stat = &stats.0["filter rejections"];
+            assert_eq!(stat.total_counter().value(), Some(100));
+        }
+    }
+
+    #[test]
+    fn filter_fulfills_die_contract() {
+        crate::check_die(dice::vec(dice::u8(..), ..).filter(|vec| vec.len() % 2 == 0));
+        crate::check_die(dice::u64(..).filter_map(|x| x.checked_mul(3)));
+    }
+
+    #[test]
+    fn limit_adapters_use_expected_limit() {
+        Dicetest::repeatedly().run(|mut fate| {
+            // Larger limits would lose precision when scaled
+            let limit = fate.roll(dice::u64(..=u64::from(u32::MAX)));
+            let fixed_limit = fate.roll(dice::u64(..));
+            let limit_die = dice::from_fn(|fate| fate.limit());
+            let mut fate = fate.with_limit(Limit(limit));
+
+            hint_debug!(limit);
+            hint_debug!(fixed_limit);
+
+            let with_limit = fate.roll((&limit_die).with_limit(Limit(fixed_limit)));
+            assert_eq!(with_limit, Limit(fixed_limit));
+
+            let scaled_limit = fate.roll((&limit_die).scale_limit(0.5));
+            assert_eq!(scaled_limit, Limit(limit / 2));
+
+            let mapped_limit = fate.roll((&limit_die).map_limit(|limit| Limit(limit.0 / 3)));
+            assert_eq!(mapped_limit, Limit(limit / 3));
+
+            let independent_limit = fate.roll((&limit_die).limit_independent());
+            assert_eq!(independent_limit, Limit::default());
+        })
+    }
+
+    #[test]
+    fn scale_limit_saturates() {
+        let mut prng = Prng::from_seed(Seed(42));
+        let limit_die = dice::from_fn(|fate| fate.limit());
+        let limit = Fate::new(&mut prng, Limit(u64::MAX)).roll(limit_die.scale_limit(2.0));
+        assert_eq!(limit, Limit(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be non-negative")]
+    fn scale_limit_panics_if_factor_is_negative() {
+        let _ = dice::u8(..).scale_limit(-1.0);
+    }
+
+    #[test]
+    fn limit_adapters_fulfill_die_contract() {
+        let vec_die = || dice::vec(dice::u8(..), ..);
+        crate::check_die(vec_die().with_limit(Limit(5)));
+        crate::check_die(vec_die().scale_limit(0.5));
+        crate::check_die(vec_die().map_limit(|limit| Limit(limit.0 / 2)));
+        crate::check_die(vec_die().limit_independent());
+    }
+}
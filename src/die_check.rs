@@ -0,0 +1,405 @@
+//! Provides functions for checking the contract of user-written generators.
+//!
+//! The docs of [`Die`] demand that the generation is deterministic and that [`Die::roll`]
+//! and [`DieOnce::roll_once`] produce the same value for the same [`Fate`]. The docs of
+//! [`Limit`] recommend that the generation is in `O(limit)`. The function [`check_die`] checks
+//! these rules and panics with the offending seed if a rule is violated.
+//!
+//! The values are compared by their [`Debug`] representation. This allows to detect hidden
+//! nondeterminism like the iteration order of a [`HashMap`](std::collections::HashMap) even
+//! if the type implements [`PartialEq`] in an order-independent way.
+//!
+//! [`DieOnce::roll_once`]: crate::DieOnce::roll_once
+
+use std::fmt::{self, Debug};
+
+use crate::runner::LimitSeries;
+use crate::{Die, Fate, Limit, Prng, Seed};
+
+// Exponents above this threshold are considered as super-linear. The threshold leaves
+// some room for noise.
+const SUPER_LINEAR_EXPONENT: f64 = 1.5;
+
+/// The configuration for [`check_die_with_config`].
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The seed for generating the seeds of the rolls.
+    pub seed: Seed,
+    /// The limit used for the first roll. The following rolls use an interpolated limit
+    /// between [`start_limit`] and [`end_limit`].
+    ///
+    /// [`start_limit`]: Config::start_limit
+    /// [`end_limit`]: Config::end_limit
+    pub start_limit: Limit,
+    /// The limit used for the last roll.
+    pub end_limit: Limit,
+    /// Defines how many values will be generated.
+    pub rolls: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            seed: Seed(0x5EED),
+            start_limit: Limit(1),
+            end_limit: Limit(100),
+            rolls: 200,
+        }
+    }
+}
+
+/// A violation of the contract of [`Die`].
+#[derive(Debug, Clone)]
+pub enum Violation {
+    /// Rolling twice with the same seed and limit produced different values.
+    Nondeterministic {
+        seed: Seed,
+        limit: Limit,
+        first: String,
+        second: String,
+    },
+    /// [`Die::roll`] and [`DieOnce::roll_once`] produced different values for the same seed
+    /// and limit.
+    ///
+    /// [`DieOnce::roll_once`]: crate::DieOnce::roll_once
+    RollOnceDiffers {
+        seed: Seed,
+        limit: Limit,
+        roll: String,
+        roll_once: String,
+    },
+    /// The size of the generated values grows super-linearly with the limit.
+    ///
+    /// The size of a value is the length of its [`Debug`] representation. The generation of a
+    /// value takes at least as much time as its size, hence super-linear growing sizes imply
+    /// a generation that is not in `O(limit)`.
+    SuperLinearSize {
+        seed: Seed,
+        limit: Limit,
+        size: usize,
+        exponent: f64,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Nondeterministic {
+                seed,
+                limit,
+                first,
+                second,
+            } => write!(
+                f,
+                "The die is nondeterministic, rolling twice with seed {} and limit {} \
+                 produced\n{first}\nand\n{second}",
+                seed.0, limit.0
+            ),
+            Violation::RollOnceDiffers {
+                seed,
+                limit,
+                roll,
+                roll_once,
+            } => write!(
+                f,
+                "The methods `roll` and `roll_once` of the die disagree, rolling with seed {} \
+                 and limit {} produced\n{roll}\nand\n{roll_once}",
+                seed.0, limit.0
+            ),
+            Violation::SuperLinearSize {
+                seed,
+                limit,
+                size,
+                exponent,
+            } => write!(
+                f,
+                "The size of the generated values grows super-linearly with the limit \
+                 (exponent {exponent:.2}), rolling with seed {} and limit {} produced a value \
+                 with size {size}",
+                seed.0, limit.0
+            ),
+        }
+    }
+}
+
+/// Checks whether the [`Die`] fulfills its contract and panics if not.
+///
+/// The default [`Config`] is used. See [`check_die_with_config`] for more details.
+///
+/// # Panics
+///
+/// Panics if a [`Violation`] was found.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+///
+/// let die = dice::vec(dice::u8(..), ..).map(|mut vec| {
+///     vec.sort();
+///     vec
+/// });
+/// dicetest::check_die(die);
+/// ```
+///
+/// ```should_panic
+/// use std::collections::HashSet;
+/// use dicetest::prelude::*;
+///
+/// // The iteration order of `HashSet` with `RandomState` is nondeterministic
+/// let die = dice::vec(dice::u8(..), 2..).map(|vec| {
+///     let set = vec.into_iter().collect::<HashSet<_>>();
+///     set.into_iter().collect::<Vec<_>>()
+/// });
+/// dicetest::check_die(die);
+/// ```
+pub fn check_die<T: Debug>(die: impl Die<T>) {
+    check_die_with_config(die, &Config::default())
+}
+
+/// Checks whether the [`Die`] fulfills its contract with the given [`Config`] and panics if
+/// not.
+///
+/// For each roll a seed is generated. The die is rolled twice with a [`Prng`] created from
+/// this seed and the [`Debug`] representations of both values are compared. After all rolls
+/// the die is consumed with [`DieOnce::roll_once`] and compared with the last roll. Finally the
+/// growth of the value sizes is estimated.
+///
+/// [`DieOnce::roll_once`]: crate::DieOnce::roll_once
+///
+/// A reported seed and limit can be used for reproducing the values:
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::{Limit, Prng, Seed};
+///
+/// let (seed, limit) = (Seed(42), Limit(10));
+/// let mut prng = Prng::from_seed(seed);
+/// let value = Fate::new(&mut prng, limit).roll(dice::u8(..));
+/// ```
+///
+/// # Panics
+///
+/// Panics if a [`Violation`] was found.
+pub fn check_die_with_config<T: Debug>(die: impl Die<T>, config: &Config) {
+    if let Err(violation) = find_violation(die, config) {
+        panic!("{violation}");
+    }
+}
+
+fn find_violation<T: Debug>(die: impl Die<T>, config: &Config) -> Result<(), Violation> {
+    let limit_series = LimitSeries::new(config.start_limit, config.end_limit, config.rolls);
+    let mut seed_prng = Prng::from_seed(config.seed);
+
+    let roll_with_seed = |seed: Seed, limit: Limit| {
+        let mut prng = Prng::from_seed(seed);
+        format!("{:?}", die.roll(Fate::new(&mut prng, limit)))
+    };
+
+    let mut largest = None;
+    let mut last = None;
+    let mut points = Vec::new();
+
+    for limit in limit_series.into_iter() {
+        let seed = Seed(seed_prng.next_number());
+        let first = roll_with_seed(seed, limit);
+        let second = roll_with_seed(seed, limit);
+
+        if first != second {
+            return Err(Violation::Nondeterministic {
+                seed,
+                limit,
+                first,
+                second,
+            });
+        }
+
+        let size = first.len();
+        if limit.0 > 0 {
+            points.push((limit.0 as f64, size as f64));
+        }
+        if largest.is_none_or(|(_, _, largest_size)| size > largest_size) {
+            largest = Some((seed, limit, size));
+        }
+        last = Some((seed, limit, first));
+    }
+
+    if let Some((seed, limit, roll)) = last {
+        let mut prng = Prng::from_seed(seed);
+        let roll_once = format!("{:?}", die.roll_once(Fate::new(&mut prng, limit)));
+
+        if roll != roll_once {
+            return Err(Violation::RollOnceDiffers {
+                seed,
+                limit,
+                roll,
+                roll_once,
+            });
+        }
+    }
+
+    if let (Some(exponent), Some((seed, limit, size))) = (growth_exponent(&points), largest) {
+        if exponent > SUPER_LINEAR_EXPONENT {
+            return Err(Violation::SuperLinearSize {
+                seed,
+                limit,
+                size,
+                exponent,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// The number of groups of consecutive rolls whose mean sizes are compared.
+const SIZE_GROUPS: usize = 10;
+
+// Estimates the exponent of the growth of the sizes with the limits.
+//
+// The rolls are grouped by their limits and the slope of the mean sizes is fitted in log-log
+// space. Unlike the logarithms of single sizes, the mean size grows with the same exponent as
+// the expected size, even if the die generates tiny values once in a while.
+fn growth_exponent(limits_and_sizes: &[(f64, f64)]) -> Option<f64> {
+    let group_len = limits_and_sizes.len().div_ceil(SIZE_GROUPS).max(1);
+    let points = limits_and_sizes
+        .chunks(group_len)
+        .map(|group| {
+            let n = group.len() as f64;
+            let mean_limit = group.iter().map(|(limit, _)| limit).sum::<f64>() / n;
+            let mean_size = group.iter().map(|(_, size)| size).sum::<f64>() / n;
+            (mean_limit.ln(), mean_size.max(1.0).ln())
+        })
+        .collect::<Vec<_>>();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+
+    if variance == 0.0 {
+        None
+    } else {
+        Some(covariance / variance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use crate::die_check::{Config, Violation, find_violation};
+    use crate::prelude::*;
+    use crate::{DieOnce, Fate, Limit, Prng};
+
+    #[test]
+    fn accepts_dice_of_this_crate() {
+        let config = Config::default();
+        assert!(find_violation(dice::u64(..), &config).is_ok());
+        assert!(find_violation(dice::string(dice::char(), ..), &config).is_ok());
+        assert!(find_violation(dice::outer_vec(dice::vec(dice::u8(..), ..), ..), &config).is_ok());
+        assert!(find_violation(dice::hash_set(dice::u8(..), ..), &config).is_ok());
+        assert!(
+            find_violation(
+                dice::b_tree_map(dice::zip().two(dice::u8(..), dice::bool()), ..),
+                &config
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn detects_global_state() {
+        let counter = AtomicU64::new(0);
+        let die = dice::from_fn(|_| counter.fetch_add(1, Ordering::Relaxed));
+
+        let violation = find_violation(die, &Config::default());
+        assert!(matches!(violation, Err(Violation::Nondeterministic { .. })));
+    }
+
+    #[test]
+    fn detects_hash_map_iteration_order() {
+        let die = dice::vec(dice::zip().two(dice::u64(..), dice::u8(..)), 10..).map(|vec| {
+            let map = vec.into_iter().collect::<HashMap<_, _>>();
+            map.into_iter().collect::<Vec<_>>()
+        });
+
+        let violation = find_violation(die, &Config::default());
+        assert!(matches!(violation, Err(Violation::Nondeterministic { .. })));
+    }
+
+    struct DisagreeingDie;
+
+    impl DieOnce<bool> for DisagreeingDie {
+        fn roll_once(self, _fate: Fate) -> bool {
+            true
+        }
+    }
+
+    impl Die<bool> for DisagreeingDie {
+        fn roll(&self, _fate: Fate) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn detects_disagreeing_roll_once() {
+        let violation = find_violation(DisagreeingDie, &Config::default());
+        assert!(matches!(violation, Err(Violation::RollOnceDiffers { .. })));
+    }
+
+    #[test]
+    fn detects_super_linear_size() {
+        let die = dice::from_fn(|fate| {
+            let limit = fate.limit().saturating_to_usize();
+            vec![0u8; limit * limit]
+        });
+
+        let violation = find_violation(die, &Config::default());
+        assert!(matches!(violation, Err(Violation::SuperLinearSize { .. })));
+
+        // Each inner vec uses the whole limit
+        let die = dice::vec(dice::vec(dice::u8(..), ..), ..);
+
+        let violation = find_violation(die, &Config::default());
+        assert!(matches!(violation, Err(Violation::SuperLinearSize { .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "seed 13652682947166142570 and limit 1")]
+    fn check_die_reports_seed() {
+        let counter = AtomicU64::new(0);
+        crate::check_die(dice::from_fn(|_| counter.fetch_add(1, Ordering::Relaxed)));
+    }
+
+    #[test]
+    fn reported_seed_reproduces_value() {
+        let die = dice::vec(dice::u8(..), ..);
+        let counter = AtomicU64::new(0);
+        let nondeterministic_die =
+            dice::from_fn(|mut fate| (fate.roll(&die), counter.fetch_add(1, Ordering::Relaxed)));
+        let Err(Violation::Nondeterministic {
+            seed, limit, first, ..
+        }) = find_violation(nondeterministic_die, &Config::default())
+        else {
+            panic!("Expected violation")
+        };
+
+        let mut prng = Prng::from_seed(seed);
+        let value = Fate::new(&mut prng, limit).roll(&die);
+        assert!(first.starts_with(&format!("({value:?}, ")));
+        assert_eq!(limit, Limit(1));
+    }
+}
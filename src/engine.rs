@@ -0,0 +1,182 @@
+//! Provides the algorithms that can be used by [`Prng`] for generating pseudorandom numbers.
+//!
+//! Each algorithm implements the trait [`Engine`] and has a corresponding [`EngineKind`]. A
+//! [`Prng`] can be created from any engine of this module and records which engine it uses,
+//! hence run codes can be decoded regardless of the chosen engine.
+//!
+//! Only the engines of this module are supported. A run code must identify its engine, hence
+//! [`Engine`] is sealed and can't be implemented outside of this crate.
+//!
+//! [`Prng`]: crate::Prng
+
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+use crate::Seed;
+
+/// A source of pseudorandom numbers.
+///
+/// This trait is sealed and implemented only by the engines of this module.
+pub trait Engine: sealed::Sealed + Debug + Clone + Eq {
+    /// The kind of the engine. It identifies the engine in run codes.
+    const KIND: EngineKind;
+
+    /// Creates an engine whose internal state is initialized with the given seed.
+    fn from_seed(seed: Seed) -> Self;
+
+    /// Returns the next pseudorandom number.
+    fn next_number(&mut self) -> u64;
+
+    /// Reinitialize the internal state of `self` using the current internal state and the given
+    /// seed.
+    fn reseed(&mut self, seed: Seed);
+
+    /// Splits off a new engine from `self`. The internal state of the new engine is generated
+    /// with `self`.
+    fn fork(&mut self) -> Self {
+        let random_number = self.next_number();
+        let mut reseeded_engine = self.clone();
+        reseeded_engine.reseed(random_number.into());
+        reseeded_engine
+    }
+
+    /// Returns the internal state as bytes.
+    ///
+    /// This function is a left inverse for [`Engine::from_bytes`].
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Creates an engine using the given bytes as internal state.
+    ///
+    /// Returns `None` if the bytes are not a valid internal state. Only bytes returned by
+    /// [`Engine::to_bytes`] should be passed to this function. For initializing an engine with
+    /// an arbitrary seed, use [`Engine::from_seed`] instead.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Identifies an [`Engine`] of this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EngineKind {
+    /// See [`Jsf64`].
+    #[default]
+    Jsf64,
+    /// See [`Xoshiro256StarStar`].
+    Xoshiro256StarStar,
+    /// See [`SplitMix64`].
+    SplitMix64,
+}
+
+impl EngineKind {
+    /// All engine kinds.
+    pub const ALL: [EngineKind; 3] = [
+        EngineKind::Jsf64,
+        EngineKind::Xoshiro256StarStar,
+        EngineKind::SplitMix64,
+    ];
+
+    /// Returns the name of the engine. It's used by the environment variable `DICETEST_ENGINE`.
+    pub fn name(self) -> &'static str {
+        match self {
+            EngineKind::Jsf64 => "jsf64",
+            EngineKind::Xoshiro256StarStar => "xoshiro256starstar",
+            EngineKind::SplitMix64 => "splitmix64",
+        }
+    }
+
+    // The id of the engine in run codes. It must never change.
+    pub(crate) fn id(self) -> u8 {
+        match self {
+            EngineKind::Jsf64 => 0,
+            EngineKind::Xoshiro256StarStar => 1,
+            EngineKind::SplitMix64 => 2,
+        }
+    }
+
+    pub(crate) fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+}
+
+impl Display for EngineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for EngineKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| format!("Engine '{}' is unknown", s))
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Jsf64 {}
+    impl Sealed for super::Xoshiro256StarStar {}
+    impl Sealed for super::SplitMix64 {}
+}
+
+mod jsf64;
+pub use jsf64::*;
+
+mod xoshiro256_star_star;
+pub use xoshiro256_star_star::*;
+
+mod split_mix64;
+pub use split_mix64::*;
+
+// Applies SplitMix64 to the state and returns the next number. Used for seeding other engines.
+fn split_mix64_next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn bytes_to_u64s<const N: usize>(bytes: &[u8]) -> Option<[u64; N]> {
+    if bytes.len() != N * 8 {
+        return None;
+    }
+
+    let mut numbers = [0; N];
+    for (number, chunk) in numbers.iter_mut().zip(bytes.chunks_exact(8)) {
+        let mut number_bytes = [0; 8];
+        number_bytes.copy_from_slice(chunk);
+        *number = u64::from_le_bytes(number_bytes);
+    }
+    Some(numbers)
+}
+
+fn u64s_to_bytes(numbers: &[u64]) -> Vec<u8> {
+    numbers
+        .iter()
+        .flat_map(|number| number.to_le_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::engine::EngineKind;
+
+    #[test]
+    fn engine_kind_from_str_is_left_inverse() {
+        for kind in EngineKind::ALL {
+            assert_eq!(EngineKind::from_str(&kind.to_string()), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn engine_kind_from_id_is_left_inverse() {
+        for kind in EngineKind::ALL {
+            assert_eq!(EngineKind::from_id(kind.id()), Some(kind));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::num::Wrapping;
+
+use crate::Seed;
+use crate::engine::{Engine, EngineKind, bytes_to_u64s, u64s_to_bytes};
+
+/// The 64-bit variant of Bob Jenkins' small fast pseudorandom number generator.
+///
+/// The algorithms are based on [this article] by Bob Jenkins. It's the default engine of
+/// [`Prng`].
+///
+/// [this article]: http://burtleburtle.net/bob/rand/smallprng.html
+/// [`Prng`]: crate::Prng
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Jsf64 {
+    state: (u64, u64, u64, u64),
+}
+
+impl Engine for Jsf64 {
+    const KIND: EngineKind = EngineKind::Jsf64;
+
+    /// Creates an engine whose internal state is initialized with the given seed.
+    ///
+    /// The result has a satisfying cycle length.
+    fn from_seed(seed: Seed) -> Self {
+        let state = (0xf1ea_5eed, seed.0, seed.0, seed.0);
+        let mut engine = Jsf64 { state };
+        for _ in 0..20 {
+            engine.next_number();
+        }
+        engine
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    fn next_number(&mut self) -> u64 {
+        let (a, b, c, d) = self.state;
+
+        // We use `Wrapping` because overflow and underflow is intended
+        let Wrapping(e) = Wrapping(a) - Wrapping(b.rotate_left(7));
+        let Wrapping(f) = Wrapping(b) ^ Wrapping(c.rotate_left(13));
+        let Wrapping(g) = Wrapping(c) + Wrapping(d.rotate_left(37));
+        let Wrapping(h) = Wrapping(d) + Wrapping(e);
+        let Wrapping(i) = Wrapping(e) + Wrapping(a);
+
+        self.state = (f, g, h, i);
+        i
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    fn reseed(&mut self, seed: Seed) {
+        let (a, b, c, d) = self.state;
+
+        // The implementation is inspired by ScalaCheck.
+        let n0 = (seed.0 >> 32) & 0xffff_ffff;
+        let n1 = seed.0 & 0xffff_ffff;
+
+        self.state = (a ^ n0, b ^ n1, c, d);
+
+        for _ in 0..16 {
+            self.next_number();
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let (a, b, c, d) = self.state;
+        u64s_to_bytes(&[a, b, c, d])
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let [a, b, c, d] = bytes_to_u64s(bytes)?;
+        let state = (a, b, c, d);
+        Some(Jsf64 { state })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{Engine, Jsf64};
+    use crate::prelude::*;
+
+    #[test]
+    fn from_seed_must_not_have_cycle_length_zero() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let seed = fate.roll(dice::u64(..));
+
+            let engine_init = Jsf64::from_seed(seed.into());
+            let mut engine_next = engine_init.clone();
+            let _ = engine_next.next_number();
+
+            assert_ne!(engine_init, engine_next);
+        })
+    }
+
+    #[test]
+    fn from_bytes_is_left_inverse() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let engine = Jsf64::from_seed(fate.roll(dice::u64(..)).into());
+            let bytes = engine.to_bytes();
+            assert_eq!(Jsf64::from_bytes(&bytes), Some(engine));
+        })
+    }
+}
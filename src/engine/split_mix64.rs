@@ -0,0 +1,65 @@
+use crate::Seed;
+use crate::engine::{Engine, EngineKind, bytes_to_u64s, split_mix64_next, u64s_to_bytes};
+
+/// The pseudorandom number generator SplitMix64 by Sebastiano Vigna.
+///
+/// It has only a single [`u64`] as internal state, hence it's very fast, but its statistical
+/// quality is weaker than the quality of the other engines. See [this website] for details.
+///
+/// [this website]: https://prng.di.unimi.it/splitmix64.c
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl Engine for SplitMix64 {
+    const KIND: EngineKind = EngineKind::SplitMix64;
+
+    fn from_seed(seed: Seed) -> Self {
+        SplitMix64 { state: seed.0 }
+    }
+
+    fn next_number(&mut self) -> u64 {
+        split_mix64_next(&mut self.state)
+    }
+
+    fn reseed(&mut self, seed: Seed) {
+        // Mix the seed so that similar seeds result in unrelated states.
+        let mut seed_state = seed.0;
+        self.state ^= split_mix64_next(&mut seed_state);
+        self.next_number();
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        u64s_to_bytes(&[self.state])
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let [state] = bytes_to_u64s(bytes)?;
+        Some(SplitMix64 { state })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{Engine, SplitMix64};
+    use crate::prelude::*;
+
+    #[test]
+    fn next_number_examples() {
+        // Reference values of the original implementation with the seed 1234567.
+        let mut engine = SplitMix64::from_seed(1234567.into());
+        assert_eq!(engine.next_number(), 6457827717110365317);
+        assert_eq!(engine.next_number(), 3203168211198807973);
+        assert_eq!(engine.next_number(), 9817491932198370423);
+    }
+
+    #[test]
+    fn from_bytes_is_left_inverse() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let engine = SplitMix64::from_seed(fate.roll(dice::u64(..)).into());
+            let bytes = engine.to_bytes();
+            assert_eq!(SplitMix64::from_bytes(&bytes), Some(engine));
+        })
+    }
+}
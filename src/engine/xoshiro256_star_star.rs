@@ -0,0 +1,108 @@
+use crate::Seed;
+use crate::engine::{Engine, EngineKind, bytes_to_u64s, split_mix64_next, u64s_to_bytes};
+
+/// The pseudorandom number generator xoshiro256** by David Blackman and Sebastiano Vigna.
+///
+/// It has a period of 2^256 - 1 and passes all known statistical tests. See [this website]
+/// for details.
+///
+/// [this website]: https://prng.di.unimi.it/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Xoshiro256StarStar {
+    state: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    // The all-zero state is the only invalid state, the engine would produce only zeros.
+    fn from_state(mut state: [u64; 4]) -> Self {
+        if state == [0; 4] {
+            state[0] = 0x9e37_79b9_7f4a_7c15;
+        }
+        Xoshiro256StarStar { state }
+    }
+}
+
+impl Engine for Xoshiro256StarStar {
+    const KIND: EngineKind = EngineKind::Xoshiro256StarStar;
+
+    fn from_seed(seed: Seed) -> Self {
+        let mut split_mix_state = seed.0;
+        let state = [(); 4].map(|_| split_mix64_next(&mut split_mix_state));
+        Self::from_state(state)
+    }
+
+    fn next_number(&mut self) -> u64 {
+        let s = &mut self.state;
+
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    fn reseed(&mut self, seed: Seed) {
+        let mut split_mix_state = seed.0;
+        let mut state = self.state;
+        for number in &mut state {
+            *number ^= split_mix64_next(&mut split_mix_state);
+        }
+        *self = Self::from_state(state);
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        u64s_to_bytes(&self.state)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let state = bytes_to_u64s(bytes)?;
+        if state == [0; 4] {
+            None
+        } else {
+            Some(Xoshiro256StarStar { state })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{Engine, Xoshiro256StarStar};
+    use crate::prelude::*;
+
+    #[test]
+    fn next_number_examples() {
+        // Reference values of the original implementation with the state [1, 2, 3, 4].
+        let mut engine = Xoshiro256StarStar::from_bytes(
+            &[1u64, 2, 3, 4]
+                .iter()
+                .flat_map(|n| n.to_le_bytes())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        assert_eq!(engine.next_number(), 11520);
+        assert_eq!(engine.next_number(), 0);
+        assert_eq!(engine.next_number(), 1509978240);
+        assert_eq!(engine.next_number(), 1215971899390074240);
+    }
+
+    #[test]
+    fn from_bytes_is_left_inverse() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let engine = Xoshiro256StarStar::from_seed(fate.roll(dice::u64(..)).into());
+            let bytes = engine.to_bytes();
+            assert_eq!(Xoshiro256StarStar::from_bytes(&bytes), Some(engine));
+        })
+    }
+
+    #[test]
+    fn from_bytes_rejects_zero_state() {
+        assert_eq!(Xoshiro256StarStar::from_bytes(&[0; 32]), None);
+    }
+}
@@ -1,6 +1,9 @@
 mod run_code;
 use run_code::RunCode;
 
+mod replay_code;
+use replay_code::ReplayCode;
+
 mod mode;
 use mode::Mode;
 
@@ -8,5 +11,8 @@ mod formatter;
 
 mod env;
 
+mod config_file;
+use config_file::ConfigFile;
+
 mod dicetest;
 pub use self::dicetest::Dicetest;
@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::frontend::env::{self, SectionVars};
+
+/// The name of the config file.
+pub const FILE_NAME: &str = "dicetest.toml";
+
+const SECTION_PROFILE: &str = "profile";
+const SECTION_TEST: &str = "test";
+
+/// A config file that contains values for the test parameters.
+///
+/// The file uses a subset of TOML. Its top-level keys apply to all tests. The section
+/// `[profile.<name>]` contains a named profile and the section `[test."<name>"]` contains
+/// overrides for a single test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    path: String,
+    root: Section,
+    profiles: BTreeMap<String, Section>,
+    tests: BTreeMap<String, Section>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section {
+    location: String,
+    values: BTreeMap<&'static str, String>,
+}
+
+impl Section {
+    fn new(location: String) -> Self {
+        Section {
+            location,
+            values: BTreeMap::new(),
+        }
+    }
+}
+
+enum SectionId {
+    Root,
+    Profile(String),
+    Test(String),
+}
+
+impl ConfigFile {
+    /// Searches the config file in the directory `CARGO_MANIFEST_DIR` and parses it.
+    ///
+    /// Returns `None` if the directory is unknown or the file doesn't exist.
+    pub fn load() -> Result<Option<Self>, String> {
+        let Some(dir) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+            return Ok(None);
+        };
+
+        let path = Path::new(&dir).join(FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!(
+                "Config file '{}' could not be read: {}",
+                path.display(),
+                err
+            )),
+            Ok(content) => Self::parse(&path.display().to_string(), &content).map(Some),
+        }
+    }
+
+    /// Parses the content of the config file. The path is only used for error messages.
+    pub fn parse(path: &str, content: &str) -> Result<Self, String> {
+        let mut config_file = ConfigFile {
+            path: path.to_string(),
+            root: Section::new(format!("'{}'", path)),
+            profiles: BTreeMap::new(),
+            tests: BTreeMap::new(),
+        };
+        let mut current = SectionId::Root;
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let error = |message: String| {
+                format!(
+                    "Config file '{}' is invalid at line {}: {}",
+                    path,
+                    index + 1,
+                    message
+                )
+            };
+
+            let line = strip_comment(raw_line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("Section header must end with ']'".to_string()))?;
+                let keys = parse_dotted_key(header).map_err(error)?;
+                let location = format!("section [{}] of '{}'", header.trim(), path);
+
+                let (id, sections, name) = match keys.as_slice() {
+                    [kind, name] if kind == SECTION_PROFILE => (
+                        SectionId::Profile(name.clone()),
+                        &mut config_file.profiles,
+                        name,
+                    ),
+                    [kind, name] if kind == SECTION_TEST => {
+                        (SectionId::Test(name.clone()), &mut config_file.tests, name)
+                    }
+                    _ => {
+                        return Err(error(format!(
+                            "Section [{}] is unknown, expected [{}.<name>] or [{}.\"<name>\"]",
+                            header.trim(),
+                            SECTION_PROFILE,
+                            SECTION_TEST,
+                        )));
+                    }
+                };
+
+                if sections.contains_key(name) {
+                    return Err(error(format!(
+                        "Section [{}] is defined twice",
+                        header.trim()
+                    )));
+                }
+
+                sections.insert(name.clone(), Section::new(location));
+                current = id;
+            } else {
+                let (key, value) = line
+                    .split_once('=')
+                    .ok_or_else(|| error("Expected 'key = value'".to_string()))?;
+                let key = key.trim();
+                if let Some(env_key) = env::env_only_key(key) {
+                    return Err(error(format!(
+                        "Key '{}' is not supported, use environment variable '{}' instead",
+                        key, env_key
+                    )));
+                }
+                let env_key =
+                    env::env_key(key).ok_or_else(|| error(format!("Key '{}' is unknown", key)))?;
+                let value = parse_value(value.trim()).map_err(error)?;
+
+                let section = match &current {
+                    SectionId::Root => &mut config_file.root,
+                    SectionId::Profile(name) => config_file.profiles.get_mut(name).unwrap(),
+                    SectionId::Test(name) => config_file.tests.get_mut(name).unwrap(),
+                };
+
+                if section.values.insert(env_key, value).is_some() {
+                    return Err(error(format!("Key '{}' is defined twice", key)));
+                }
+            }
+        }
+
+        Ok(config_file)
+    }
+
+    /// Returns the variables that apply to the given profile and test name.
+    ///
+    /// The variables are returned in the order they need to be applied: top-level keys,
+    /// the profile and finally the test overrides.
+    pub fn vars(
+        &self,
+        profile: Option<&str>,
+        test_name: Option<&str>,
+    ) -> Result<Vec<SectionVars<'_>>, String> {
+        let mut sections = vec![&self.root];
+
+        if let Some(profile) = profile {
+            let section = self.profiles.get(profile).ok_or_else(|| {
+                format!(
+                    "Profile '{}' is not defined in config file '{}'",
+                    profile, self.path
+                )
+            })?;
+            sections.push(section);
+        }
+
+        if let Some(section) = test_name.and_then(|test_name| self.tests.get(test_name)) {
+            sections.push(section);
+        }
+
+        let vars = sections
+            .into_iter()
+            .map(|section| SectionVars {
+                location: &section.location,
+                values: &section.values,
+            })
+            .collect();
+
+        Ok(vars)
+    }
+}
+
+// Removes the comment from the line. A `#` inside of a string doesn't start a comment.
+fn strip_comment(line: &str) -> &str {
+    let mut inside_string = false;
+    let mut escaped = false;
+
+    for (index, char) in line.char_indices() {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' if inside_string => escaped = true,
+            '"' => inside_string = !inside_string,
+            '#' if !inside_string => return &line[..index],
+            _ => (),
+        }
+    }
+
+    line
+}
+
+// Parses keys like `profile.ci` or `test."foo::bar"`.
+fn parse_dotted_key(s: &str) -> Result<Vec<String>, String> {
+    let mut keys = Vec::new();
+    let mut rest = s.trim();
+
+    loop {
+        let (key, remainder) = if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| format!("Key '{}' has no closing quote", s.trim()))?;
+            (quoted[..end].to_string(), &quoted[end + 1..])
+        } else {
+            let end = rest.find('.').unwrap_or(rest.len());
+            let key = rest[..end].trim();
+            let is_bare_key = !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !is_bare_key {
+                return Err(format!("Key '{}' is invalid", s.trim()));
+            }
+            (key.to_string(), &rest[end..])
+        };
+
+        keys.push(key);
+
+        let remainder = remainder.trim_start();
+        if remainder.is_empty() {
+            return Ok(keys);
+        }
+
+        rest = remainder
+            .strip_prefix('.')
+            .ok_or_else(|| format!("Key '{}' is invalid", s.trim()))?
+            .trim_start();
+    }
+}
+
+// Parses a string, integer, float or bool. The value is returned in the same representation
+// as an environment variable, e.g. the underscores of `1_000` are removed.
+fn parse_value(s: &str) -> Result<String, String> {
+    if let Some(quoted) = s.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.chars();
+
+        loop {
+            match chars.next() {
+                None => return Err(format!("String {} has no closing quote", s)),
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    _ => return Err(format!("String {} contains an invalid escape", s)),
+                },
+                Some(char) => value.push(char),
+            }
+        }
+
+        if chars.as_str().trim().is_empty() {
+            Ok(value)
+        } else {
+            Err(format!("String {} is followed by unexpected characters", s))
+        }
+    } else if s.is_empty() || s.contains(char::is_whitespace) {
+        Err(format!("Value '{}' is invalid", s))
+    } else if s.contains('_') {
+        // Like in TOML an underscore must be surrounded by digits
+        let chars = s.chars().collect::<Vec<_>>();
+        let is_valid = chars.iter().enumerate().all(|(index, &char)| {
+            char != '_'
+                || (index > 0
+                    && chars[index - 1].is_ascii_digit()
+                    && chars.get(index + 1).is_some_and(char::is_ascii_digit))
+        });
+        if is_valid {
+            Ok(s.replace('_', ""))
+        } else {
+            Err(format!("Value '{}' has an invalid underscore", s))
+        }
+    } else {
+        Ok(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::config_file::ConfigFile;
+    use crate::frontend::env::Vars;
+
+    const EXAMPLE: &str = r#"
+# Applies to all tests
+passes = 100
+stats_enabled = false
+
+[profile.quick]
+passes_multiplier = 0.1 # Run fewer passes
+
+[profile.nightly]
+passes_multiplier = 10
+limit_multiplier = "none"
+
+[test."tests::slow_test"]
+passes = 10
+"#;
+
+    fn collect(
+        config_file: &ConfigFile,
+        profile: Option<&str>,
+        test_name: Option<&str>,
+    ) -> Vec<(String, Option<String>)> {
+        config_file
+            .vars(profile, test_name)
+            .unwrap()
+            .iter()
+            .flat_map(|vars| {
+                [
+                    "DICETEST_PASSES",
+                    "DICETEST_PASSES_MULTIPLIER",
+                    "DICETEST_LIMIT_MULTIPLIER",
+                ]
+                .into_iter()
+                .map(|key| (key.to_string(), vars.var(key).ok()))
+                .filter(|(_, value)| value.is_some())
+                .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn pair(key: &str, value: &str) -> (String, Option<String>) {
+        (key.to_string(), Some(value.to_string()))
+    }
+
+    #[test]
+    fn parse_example_without_profile() {
+        let config_file = ConfigFile::parse("dicetest.toml", EXAMPLE).unwrap();
+        assert_eq!(
+            collect(&config_file, None, Some("tests::other_test")),
+            vec![pair("DICETEST_PASSES", "100")]
+        );
+    }
+
+    #[test]
+    fn parse_example_with_profile_and_test() {
+        let config_file = ConfigFile::parse("dicetest.toml", EXAMPLE).unwrap();
+        assert_eq!(
+            collect(&config_file, Some("nightly"), Some("tests::slow_test")),
+            vec![
+                pair("DICETEST_PASSES", "100"),
+                pair("DICETEST_PASSES_MULTIPLIER", "10"),
+                pair("DICETEST_LIMIT_MULTIPLIER", "none"),
+                pair("DICETEST_PASSES", "10"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_profile_is_error() {
+        let config_file = ConfigFile::parse("dicetest.toml", EXAMPLE).unwrap();
+        assert!(config_file.vars(Some("ci"), None).is_err());
+    }
+
+    #[test]
+    fn env_only_key_is_error() {
+        for key in ["mode", "debug", "replay", "seed"] {
+            let result = ConfigFile::parse("dicetest.toml", &format!("{key} = \"foo\""));
+            let error = result.unwrap_err();
+            assert!(
+                error.contains(&format!("Key '{key}' is not supported")),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn underscores_of_numbers_are_removed() {
+        let config_file = ConfigFile::parse("dicetest.toml", "passes = 1_000").unwrap();
+        assert_eq!(
+            collect(&config_file, None, None),
+            vec![pair("DICETEST_PASSES", "1000")]
+        );
+
+        let config_file = ConfigFile::parse("dicetest.toml", "passes = \"1_000\"").unwrap();
+        assert_eq!(
+            collect(&config_file, None, None),
+            vec![pair("DICETEST_PASSES", "1_000")]
+        );
+
+        assert!(ConfigFile::parse("dicetest.toml", "passes = 1__000").is_err());
+        assert!(ConfigFile::parse("dicetest.toml", "passes = _1").is_err());
+        assert!(ConfigFile::parse("dicetest.toml", "passes = 1_").is_err());
+    }
+
+    #[test]
+    fn unknown_key_is_error() {
+        let result = ConfigFile::parse("dicetest.toml", "pases = 100");
+        assert!(result.unwrap_err().contains("Key 'pases' is unknown"));
+    }
+
+    #[test]
+    fn unknown_section_is_error() {
+        let result = ConfigFile::parse("dicetest.toml", "[profiles.ci]");
+        assert!(
+            result
+                .unwrap_err()
+                .contains("Section [profiles.ci] is unknown")
+        );
+    }
+
+    #[test]
+    fn duplicate_key_is_error() {
+        let result = ConfigFile::parse("dicetest.toml", "[profile.ci]\npasses = 1\npasses = 2");
+        assert!(result.unwrap_err().contains("line 3"));
+    }
+
+    #[test]
+    fn duplicate_section_is_error() {
+        let result = ConfigFile::parse("dicetest.toml", "[profile.ci]\n[profile.ci]");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn invalid_string_is_error() {
+        let result = ConfigFile::parse("dicetest.toml", "mode = \"once");
+        assert!(result.is_err());
+    }
+}
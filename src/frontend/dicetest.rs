@@ -1,16 +1,19 @@
 use std::panic::{self, RefUnwindSafe, UnwindSafe};
 use std::str::FromStr;
+use std::thread;
 
-use crate::frontend::env::{self, EnvValue};
+use crate::engine::EngineKind;
+use crate::frontend::env::{self, EnvValue, Vars};
 use crate::frontend::formatter::*;
-use crate::frontend::{Mode, RunCode};
+use crate::frontend::{ConfigFile, Mode, ReplayCode, RunCode, config_file};
 use crate::{Fate, Limit, Prng, Seed, runner};
 
 #[derive(Debug, Clone)]
 struct Params {
-    regressions: Vec<runner::repeatedly::Regression>,
+    regressions: Vec<RunCode>,
     regressions_enabled: bool,
     seed: Option<Seed>,
+    engine: EngineKind,
     once_limit: Limit,
     start_limit: Limit,
     end_limit: Limit,
@@ -29,6 +32,7 @@ impl Default for Params {
             regressions: Vec::new(),
             regressions_enabled: true,
             seed: None,
+            engine: EngineKind::default(),
             once_limit: Limit::default(),
             start_limit: 0.into(),
             end_limit: Limit::default(),
@@ -45,7 +49,45 @@ impl Default for Params {
 
 /// Front end for configuring and running a test with pseudorandomly generated test data.
 ///
-/// You can set the test parameters via source code or environment variables.
+/// You can set the test parameters via source code, environment variables or a config file.
+///
+/// # Config file
+///
+/// If a file named `dicetest.toml` exists in the directory `CARGO_MANIFEST_DIR`, it will be
+/// used for overriding the test parameters. The file uses a subset of TOML. Each environment
+/// variable `DICETEST_<KEY>` has a corresponding key `<key>` written in lowercase, e.g.
+/// `passes` for `DICETEST_PASSES`. The values have the same format as the values of the
+/// environment variables. The environment variables `DICETEST_PROFILE`, `DICETEST_MODE`,
+/// `DICETEST_DEBUG`, `DICETEST_REPLAY` and `DICETEST_SEED` have no key because they would pin
+/// all test runs to a single mode or test run.
+///
+/// ```toml
+/// # Top-level keys apply to all tests.
+/// stats_enabled = true
+///
+/// # A profile applies only if it's selected via `DICETEST_PROFILE=<name>`.
+/// [profile.quick]
+/// passes_multiplier = 0.1
+///
+/// [profile.nightly]
+/// passes_multiplier = 10
+/// limit_multiplier = 2
+///
+/// # Overrides for a single test, keyed by the name of the test.
+/// [test."tests::my_slow_test"]
+/// passes = 20
+/// ```
+///
+/// The test name is taken from the name of the current thread, which is set by the
+/// default test harness. If the thread isn't named after the test, the test overrides don't
+/// apply. This happens e.g. with a custom test harness, if the test runs [`Dicetest`] on a
+/// thread spawned by itself or with older Rust versions that run the tests on the main thread
+/// if `--test-threads=1` is used.
+///
+/// The values are applied in the following order, later values override earlier ones:
+/// source code, top-level keys, the selected profile, the test overrides and finally the
+/// environment variables. Unknown keys in the config file and unknown environment variables
+/// with the prefix `DICETEST_` are rejected.
 ///
 /// # Examples
 ///
@@ -71,7 +113,7 @@ impl Default for Params {
 /// ```
 /// use dicetest::Dicetest;
 ///
-/// Dicetest::debug("3lTBtDxQx6SneW3r4sNLUVoYAREJ8OuO9B0yp31nna0NdwFGFvA4no").run(|fate| {
+/// Dicetest::debug("FFzBq0YmfzhiePKlkHMkykx5yHDPGK4AkoM6tF1kmcy0GUUSyu8LZWvUnhLU35WPo").run(|fate| {
 ///     // Put your test here.
 /// });
 /// ```
@@ -89,11 +131,13 @@ impl Dicetest {
     /// will be extracted from the given run code.
     ///
     /// Please note that run codes are not guaranteed to be stable over time. Library updates
-    /// might change the value generators so that different values will be generated.
+    /// might change the value generators so that different values will be generated. A run code
+    /// contains the version of dicetest that created it. If it differs from the current version,
+    /// the test result will contain a warning.
     ///
     /// # Panics
     ///
-    /// Panics if the run code is invalid.
+    /// Panics if the run code is invalid, e.g. if its checksum doesn't match due to a typo.
     ///
     /// # Environment variable
     ///
@@ -108,6 +152,32 @@ impl Dicetest {
         }
     }
 
+    /// Configuration for running the test in replay mode.
+    ///
+    /// In this mode a single random test run of the run-repeatedly mode will be run once. It's
+    /// identified by the seed and the index of the pass (both printed when a test had failed).
+    /// The parameters for pseudorandom value generation will be reconstructed by fast-forwarding
+    /// the run-repeatedly mode.
+    ///
+    /// In contrast to a run code, the seed and the pass only identify the test run if the
+    /// parameters [`Dicetest::engine`], [`Dicetest::start_limit`], [`Dicetest::end_limit`] and
+    /// [`Dicetest::passes`] (and their multipliers) have the same values as in the original run.
+    ///
+    /// # Panics
+    ///
+    /// The test will panic if the pass is not less than the number of passes.
+    ///
+    /// # Environment variable
+    ///
+    /// You can set this mode with `DICETEST_REPLAY=<seed>:<pass>`. The values `<seed>` and
+    /// `<pass>` must be `<u64>`.
+    pub fn replay(seed: Seed, pass: u64) -> Self {
+        Dicetest {
+            mode: Mode::Replay(ReplayCode { seed, pass }),
+            params: Params::default(),
+        }
+    }
+
     /// Configuration for running the test in run-once mode.
     ///
     /// In this mode the test will be run once. In contrast to debug mode, the [`Seed`] and
@@ -144,6 +214,33 @@ impl Dicetest {
         }
     }
 
+    /// Configuration for running the test in sample mode.
+    ///
+    /// In this mode the test will be run the given number of times for previewing the generated
+    /// test data. Each sample uses the [`Prng`] of the pass with the same index in the
+    /// run-repeatedly mode, see [`Dicetest::repeatedly`]. The limits are interpolated between
+    /// the start limit and the end limit over the samples instead of the passes.
+    ///
+    /// The test is a closure, hence its assertions are executed as well. A failed assertion
+    /// doesn't abort the sampling, but is printed as the error of its sample.
+    ///
+    /// The hints of each test run are printed, hence you should add the rolled values as hints,
+    /// e.g. via [`hint_debug`]. The stats of all test runs are printed as histograms.
+    ///
+    /// [`hint_debug`]: crate::hint_debug
+    ///
+    /// # Environment variable
+    ///
+    /// You can set this mode via `DICETEST_MODE=sample`. In this case the test will be run
+    /// 10 times. Use `DICETEST_MODE=sample:<samples>` for another number of samples, e.g.
+    /// `DICETEST_MODE=sample:100`.
+    pub fn sample(samples: u64) -> Self {
+        Dicetest {
+            mode: Mode::Sample(samples),
+            params: Params::default(),
+        }
+    }
+
     /// Adds a regression test.
     ///
     /// The regression test is run at the beginning of the run-repeatedly mode. The parameters
@@ -153,20 +250,17 @@ impl Dicetest {
     /// [`Dicetest::regressions_enabled`].
     ///
     /// Please note that run codes are not guaranteed to be stable over time. Library updates
-    /// might change the value generators so that different values will be generated.
+    /// might change the value generators so that different values will be generated. A run code
+    /// contains the version of dicetest that created it. If it differs from the current version,
+    /// the test result will contain a warning.
     ///
     /// # Panics
     ///
-    /// Panics if the run code is invalid.
+    /// Panics if the run code is invalid, e.g. if its checksum doesn't match due to a typo.
     #[track_caller]
     pub fn regression(mut self, run_code: &str) -> Self {
         let run_code = RunCode::from_str(run_code).unwrap();
-        self.params
-            .regressions
-            .push(runner::repeatedly::Regression {
-                prng: run_code.prng,
-                limit: run_code.limit,
-            });
+        self.params.regressions.push(run_code);
         self
     }
 
@@ -199,6 +293,20 @@ impl Dicetest {
         self
     }
 
+    /// Sets the engine of the [`Prng`] used for the pseudorandom value generation.
+    ///
+    /// It's only used in run-once, run-repeatedly and replay mode and is [`EngineKind::Jsf64`]
+    /// by default. In debug mode and for regression tests the engine is taken from the run code.
+    ///
+    /// # Environment variable
+    ///
+    /// You can set this parameter via `DICETEST_ENGINE=<engine>`. The value `<engine>` must be
+    /// either `jsf64`, `xoshiro256starstar` or `splitmix64`.
+    pub fn engine(mut self, engine: EngineKind) -> Self {
+        self.params.engine = engine;
+        self
+    }
+
     /// Sets the upper limit for the length of generated dynamic data structures.
     ///
     /// It's only used in run-once mode and is `100` by default.
@@ -316,6 +424,9 @@ impl Dicetest {
     ///
     /// If `None` all values will be present in the result. This parameter is `Some(20)` by default.
     ///
+    /// The histograms of the sample mode group numeric values into at most this number of
+    /// ranges instead of omitting values.
+    ///
     /// # Environment variable
     ///
     /// You can set this parameter via `DICETEST_STATS_MAX_VALUE_COUNT=<max_value_count>`.
@@ -338,10 +449,11 @@ impl Dicetest {
         self
     }
 
-    /// Sets whether test parameters can be overridden via environment variables.
+    /// Sets whether test parameters can be overridden via environment variables and the
+    /// config file.
     ///
-    /// If set to true and special environment variables are present, [`Dicetest::run`]
-    /// will parse their values and override the corresponding test parameters
+    /// If set to true and special environment variables or the config file are present,
+    /// [`Dicetest::run`] will parse their values and override the corresponding test parameters
     /// before running the test. This parameter is `true` by default.
     pub fn env_enabled(mut self, env_enabled: bool) -> Self {
         self.params.env_enabled = env_enabled;
@@ -350,14 +462,14 @@ impl Dicetest {
 
     /// Runs the test with the given configuration and prints the result to stdout.
     ///
-    /// If special environment variables are present, this function will parse their values and
-    /// override the corresponding test parameters before running the test. This can be disabled
-    /// via [`Dicetest::env_enabled`].
+    /// If special environment variables or the config file are present, this function will parse
+    /// their values and override the corresponding test parameters before running the test.
+    /// This can be disabled via [`Dicetest::env_enabled`].
     ///
     /// # Panics
     ///
-    /// Panics if parsing a present environment variable has failed or the test has panicked
-    /// during a test run.
+    /// Panics if parsing a present environment variable or the config file has failed or the
+    /// test has panicked during a test run.
     #[track_caller]
     pub fn run<T>(self, test: T)
     where
@@ -381,10 +493,44 @@ impl Dicetest {
                 };
                 let report = runner::once::run(prng, &config, test);
 
+                let warnings = Vec::from_iter(run_code.version_warning());
+                let formatting = &params.formatting;
+                println!(
+                    "{}",
+                    display_run_once_report(&run_code, None, &report, formatting, &warnings)
+                );
+
+                if let Some(err) = report.error.map(|error| error.0) {
+                    panic::resume_unwind(err);
+                }
+            }
+            Mode::Replay(replay_code) => {
+                let prng = Prng::from_engine_seed(params.engine, replay_code.seed);
+                let repeatedly_config = repeatedly_config(&params, Vec::new());
+                let regression = runner::repeatedly::fast_forward(
+                    prng,
+                    &repeatedly_config,
+                    replay_code.pass,
+                )
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Pass {} of replay code {} is out of bounds, there are only {} passes",
+                        replay_code.pass, replay_code, repeatedly_config.passes
+                    )
+                });
+
+                let config = runner::once::Config {
+                    limit: regression.limit,
+                    hints_enabled: params.hints_enabled,
+                    stats_enabled: params.stats_enabled,
+                };
+                let report = runner::once::run(regression.prng.clone(), &config, test);
+
+                let run_code = RunCode::new(regression.prng, regression.limit);
                 let formatting = &params.formatting;
                 println!(
                     "{}",
-                    display_run_once_report(&run_code, None, &report, formatting)
+                    display_run_once_report(&run_code, None, &report, formatting, &[])
                 );
 
                 if let Some(err) = report.error.map(|error| error.0) {
@@ -393,7 +539,7 @@ impl Dicetest {
             }
             Mode::Once => {
                 let seed = params.seed.unwrap_or_else(Seed::random);
-                let prng = Prng::from_seed(seed);
+                let prng = Prng::from_engine_seed(params.engine, seed);
                 let mut limit = params.once_limit;
 
                 if let Some(limit_multiplier) = params.limit_multiplier {
@@ -407,11 +553,11 @@ impl Dicetest {
                 };
                 let report = runner::once::run(prng.clone(), &config, test);
 
-                let run_code = RunCode { prng, limit };
+                let run_code = RunCode::new(prng, limit);
                 let formatting = &params.formatting;
                 println!(
                     "{}",
-                    display_run_once_report(&run_code, Some(seed), &report, formatting)
+                    display_run_once_report(&run_code, Some(seed), &report, formatting, &[])
                 );
 
                 if let Some(err) = report.error.map(|error| error.0) {
@@ -419,88 +565,133 @@ impl Dicetest {
                 }
             }
             Mode::Repeatedly => {
-                let regressions = if params.regressions_enabled {
-                    params.regressions
+                let regression_run_codes = if params.regressions_enabled {
+                    params.regressions.clone()
                 } else {
                     Vec::new()
                 };
+                let warnings = regression_run_codes
+                    .iter()
+                    .filter_map(RunCode::version_warning)
+                    .collect::<Vec<_>>();
+                let regressions = regression_run_codes
+                    .into_iter()
+                    .map(|run_code| runner::repeatedly::Regression {
+                        prng: run_code.prng,
+                        limit: run_code.limit,
+                    })
+                    .collect();
 
                 let seed = params.seed.unwrap_or_else(Seed::random);
-                let prng = Prng::from_seed(seed);
-                let mut start_limit = params.start_limit;
-                let mut end_limit = params.end_limit;
-                let mut passes = params.passes;
-
-                if let Some(limit_multiplier) = params.limit_multiplier {
-                    start_limit = multiply(start_limit.0, limit_multiplier).into();
-                    end_limit = multiply(end_limit.0, limit_multiplier).into();
-                }
-                if let Some(passes_multiplier) = params.passes_multiplier {
-                    passes = multiply(passes, passes_multiplier);
-                }
-
-                let config = runner::repeatedly::Config {
-                    regressions,
-                    start_limit,
-                    end_limit,
-                    passes,
-                    hints_enabled: params.hints_enabled,
-                    stats_enabled: params.stats_enabled,
-                };
+                let prng = Prng::from_engine_seed(params.engine, seed);
+                let config = repeatedly_config(&params, regressions);
                 let report = runner::repeatedly::run(prng, &config, test);
 
                 let formatting = &params.formatting;
                 println!(
                     "{}",
-                    display_run_repeatedly_report(seed, &config, &report, formatting)
+                    display_run_repeatedly_report(seed, &config, &report, formatting, &warnings)
                 );
 
                 if let Some(err) = report.counterexample.map(|c| c.error.0) {
                     panic::resume_unwind(err);
                 }
             }
+            Mode::Sample(samples) => {
+                let seed = params.seed.unwrap_or_else(Seed::random);
+                let prng = Prng::from_engine_seed(params.engine, seed);
+                let repeatedly_config = repeatedly_config(&params, Vec::new());
+                let config = runner::sample::Config {
+                    start_limit: repeatedly_config.start_limit,
+                    end_limit: repeatedly_config.end_limit,
+                    samples,
+                };
+                let report = runner::sample::run(prng, &config, test);
+
+                let formatting = &params.formatting;
+                println!(
+                    "{}",
+                    display_sample_report(seed, &config, &report, formatting)
+                );
+            }
         }
     }
 
-    fn override_by_env(mut self) -> Result<Self, String> {
+    fn override_by_env(self) -> Result<Self, String> {
+        env::check_unknown_keys()?;
+
+        let profile = match env::read_profile()? {
+            EnvValue::NotPresent => None,
+            EnvValue::Present(profile) => Some(profile),
+        };
+
+        let mut config = self;
+
+        match ConfigFile::load()? {
+            Some(config_file) => {
+                let current_thread = thread::current();
+                let test_name = current_thread.name();
+                for vars in config_file.vars(profile.as_deref(), test_name)? {
+                    config = config.override_by_vars(&vars)?;
+                }
+            }
+            None => {
+                if let Some(profile) = profile {
+                    return Err(format!(
+                        "Profile '{}' is selected, but config file '{}' is missing",
+                        profile,
+                        config_file::FILE_NAME
+                    ));
+                }
+            }
+        }
+
+        config.override_by_vars(&env::ProcessVars)
+    }
+
+    fn override_by_vars(mut self, vars: &impl Vars) -> Result<Self, String> {
         // Read values
-        if let EnvValue::Present(mode) = env::read_mode()? {
+        if let EnvValue::Present(mode) = env::read_mode(vars)? {
             self.mode = mode;
         }
-        if let EnvValue::Present(regression_enabled) = env::read_regressions_enabled()? {
+        if let EnvValue::Present(regression_enabled) = env::read_regressions_enabled(vars)? {
             self.params.regressions_enabled = regression_enabled
         }
-        if let EnvValue::Present(seed) = env::read_seed()? {
+        if let EnvValue::Present(seed) = env::read_seed(vars)? {
             self.params.seed = seed
         }
-        if let EnvValue::Present(once_limit) = env::read_once_limit()? {
+        if let EnvValue::Present(engine) = env::read_engine(vars)? {
+            self.params.engine = engine
+        }
+        if let EnvValue::Present(once_limit) = env::read_once_limit(vars)? {
             self.params.once_limit = once_limit
         }
-        if let EnvValue::Present(start_limit) = env::read_start_limit()? {
+        if let EnvValue::Present(start_limit) = env::read_start_limit(vars)? {
             self.params.start_limit = start_limit
         }
-        if let EnvValue::Present(end_limit) = env::read_end_limit()? {
+        if let EnvValue::Present(end_limit) = env::read_end_limit(vars)? {
             self.params.end_limit = end_limit
         }
-        if let EnvValue::Present(limit_multiplier) = env::read_limit_multiplier()? {
+        if let EnvValue::Present(limit_multiplier) = env::read_limit_multiplier(vars)? {
             self.params.limit_multiplier = limit_multiplier;
         }
-        if let EnvValue::Present(passes) = env::read_passes()? {
+        if let EnvValue::Present(passes) = env::read_passes(vars)? {
             self.params.passes = passes
         }
-        if let EnvValue::Present(passes_multiplier) = env::read_passes_multiplier()? {
+        if let EnvValue::Present(passes_multiplier) = env::read_passes_multiplier(vars)? {
             self.params.passes_multiplier = passes_multiplier
         }
-        if let EnvValue::Present(hints_enabled) = env::read_hints_enabled()? {
+        if let EnvValue::Present(hints_enabled) = env::read_hints_enabled(vars)? {
             self.params.hints_enabled = hints_enabled
         }
-        if let EnvValue::Present(stats_enabled) = env::read_stats_enabled()? {
+        if let EnvValue::Present(stats_enabled) = env::read_stats_enabled(vars)? {
             self.params.stats_enabled = stats_enabled
         }
-        if let EnvValue::Present(stats_max_value_count) = env::read_stats_max_value_count()? {
+        if let EnvValue::Present(stats_max_value_count) = env::read_stats_max_value_count(vars)? {
             self.params.formatting.stats_max_value_count = stats_max_value_count
         }
-        if let EnvValue::Present(stats_percent_precision) = env::read_stats_percent_precision()? {
+        if let EnvValue::Present(stats_percent_precision) = env::read_stats_percent_precision(vars)?
+        {
             self.params.formatting.stats_percent_precision = stats_percent_precision
         }
 
@@ -508,6 +699,32 @@ impl Dicetest {
     }
 }
 
+fn repeatedly_config(
+    params: &Params,
+    regressions: Vec<runner::repeatedly::Regression>,
+) -> runner::repeatedly::Config {
+    let mut start_limit = params.start_limit;
+    let mut end_limit = params.end_limit;
+    let mut passes = params.passes;
+
+    if let Some(limit_multiplier) = params.limit_multiplier {
+        start_limit = multiply(start_limit.0, limit_multiplier).into();
+        end_limit = multiply(end_limit.0, limit_multiplier).into();
+    }
+    if let Some(passes_multiplier) = params.passes_multiplier {
+        passes = multiply(passes, passes_multiplier);
+    }
+
+    runner::repeatedly::Config {
+        regressions,
+        start_limit,
+        end_limit,
+        passes,
+        hints_enabled: params.hints_enabled,
+        stats_enabled: params.stats_enabled,
+    }
+}
+
 fn multiply(value: u64, factor: f64) -> u64 {
     (value as f64 * factor) as u64
 }
@@ -518,14 +735,27 @@ mod tests {
 
     #[test]
     fn set_debug() {
-        let run_code = RunCode {
-            prng: Prng::from_seed(42.into()),
-            limit: Limit::default(),
-        };
+        let run_code = RunCode::new(Prng::from_seed(42.into()), Limit::default());
         let dicetest = Dicetest::debug(&run_code.to_string());
         assert_eq!(Mode::Debug(run_code), dicetest.mode);
     }
 
+    #[test]
+    fn set_sample() {
+        let dicetest = Dicetest::sample(42);
+        assert_eq!(Mode::Sample(42), dicetest.mode);
+    }
+
+    #[test]
+    fn set_replay() {
+        let dicetest = Dicetest::replay(42.into(), 7);
+        let replay_code = ReplayCode {
+            seed: 42.into(),
+            pass: 7,
+        };
+        assert_eq!(Mode::Replay(replay_code), dicetest.mode);
+    }
+
     #[test]
     fn set_once() {
         let dicetest = Dicetest::once();
@@ -552,6 +782,13 @@ mod tests {
         assert_eq!(seed, dicetest.params.seed);
     }
 
+    #[test]
+    fn set_engine() {
+        let engine = EngineKind::Xoshiro256StarStar;
+        let dicetest = Dicetest::repeatedly().engine(engine);
+        assert_eq!(engine, dicetest.params.engine);
+    }
+
     #[test]
     fn set_once_limit() {
         let once_limit = 42.into();
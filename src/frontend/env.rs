@@ -1,13 +1,17 @@
+use std::collections::BTreeMap;
 use std::env::{self, VarError};
 use std::str::FromStr;
 
-use crate::frontend::{Mode, RunCode};
+use crate::engine::EngineKind;
+use crate::frontend::{Mode, ReplayCode, RunCode};
 use crate::{Limit, Seed};
 
 const KEY_MODE: &str = "DICETEST_MODE";
 const KEY_DEBUG: &str = "DICETEST_DEBUG";
+const KEY_REPLAY: &str = "DICETEST_REPLAY";
 const KEY_REGRESSIONS_ENABLED: &str = "DICETEST_REGRESSIONS_ENABLED";
 const KEY_SEED: &str = "DICETEST_SEED";
+const KEY_ENGINE: &str = "DICETEST_ENGINE";
 const KEY_ONCE_LIMIT: &str = "DICETEST_ONCE_LIMIT";
 const KEY_START_LIMIT: &str = "DICETEST_START_LIMIT";
 const KEY_END_LIMIT: &str = "DICETEST_END_LIMIT";
@@ -18,130 +22,364 @@ const KEY_HINTS_ENABLED: &str = "DICETEST_HINTS_ENABLED";
 const KEY_STATS_ENABLED: &str = "DICETEST_STATS_ENABLED";
 const KEY_STATS_MAX_VALUE_COUNT: &str = "DICETEST_STATS_MAX_VALUE_COUNT";
 const KEY_STATS_PERCENT_PRECISION: &str = "DICETEST_STATS_PERCENT_PRECISION";
+const KEY_PROFILE: &str = "DICETEST_PROFILE";
+
+const KEY_PREFIX: &str = "DICETEST_";
+
+// All keys that can only be used as environment variable. They select a single test run or
+// the mode, hence a config file that is checked in must not pin them for all runs.
+const ENV_ONLY_KEYS: &[&str] = &[KEY_MODE, KEY_DEBUG, KEY_REPLAY, KEY_SEED];
+
+// All keys that can be used in the config file.
+const CONFIG_KEYS: &[&str] = &[
+    KEY_REGRESSIONS_ENABLED,
+    KEY_ENGINE,
+    KEY_ONCE_LIMIT,
+    KEY_START_LIMIT,
+    KEY_END_LIMIT,
+    KEY_LIMIT_MULTIPLIER,
+    KEY_PASSES,
+    KEY_PASSES_MULTIPLIER,
+    KEY_HINTS_ENABLED,
+    KEY_STATS_ENABLED,
+    KEY_STATS_MAX_VALUE_COUNT,
+    KEY_STATS_PERCENT_PRECISION,
+];
 
 const VALUE_NONE: &str = "none";
 const VALUE_REPEATEDLY: &str = "repeatedly";
 const VALUE_ONCE: &str = "once";
+const VALUE_SAMPLE: &str = "sample";
+const VALUE_SAMPLE_PREFIX: &str = "sample:";
+
+// The number of samples if the sample mode is selected via environment variable without count.
+const DEFAULT_SAMPLES: u64 = 10;
 
 pub enum EnvValue<T> {
     NotPresent,
     Present(T),
 }
 
-pub fn read_mode() -> Result<EnvValue<Mode>, String> {
+/// A source of variables that can override the test parameters.
+///
+/// The variables are identified by the keys of the environment variables.
+pub trait Vars {
+    /// Returns the value of the variable with the given key.
+    fn var(&self, key: &str) -> Result<String, VarError>;
+
+    /// Returns a description of the variable with the given key that can be used in error
+    /// messages.
+    fn describe(&self, key: &str) -> String;
+}
+
+/// The environment variables of the current process.
+pub struct ProcessVars;
+
+impl Vars for ProcessVars {
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        env::var(key)
+    }
+
+    fn describe(&self, key: &str) -> String {
+        format!("'{}'", key)
+    }
+}
+
+/// Variables of a section of the config file.
+pub struct SectionVars<'a> {
+    /// Describes where the section can be found.
+    pub location: &'a str,
+    /// Maps the keys of the environment variables to the values of the section.
+    pub values: &'a BTreeMap<&'static str, String>,
+}
+
+impl Vars for SectionVars<'_> {
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        self.values.get(key).cloned().ok_or(VarError::NotPresent)
+    }
+
+    fn describe(&self, key: &str) -> String {
+        format!("'{}' in {}", config_key(key), self.location)
+    }
+}
+
+/// Returns the key of the environment variable that belongs to the given config key.
+pub fn env_key(config_key: &str) -> Option<&'static str> {
+    CONFIG_KEYS
+        .iter()
+        .copied()
+        .find(|key| config_key == self::config_key(key))
+}
+
+/// Returns the key of the environment variable that belongs to the given config key if the
+/// key can only be used as environment variable.
+pub fn env_only_key(config_key: &str) -> Option<&'static str> {
+    ENV_ONLY_KEYS
+        .iter()
+        .copied()
+        .find(|key| config_key == self::config_key(key))
+}
+
+fn config_key(env_key: &str) -> String {
+    env_key.trim_start_matches(KEY_PREFIX).to_ascii_lowercase()
+}
+
+/// Returns an error if an environment variable with the prefix `DICETEST_` is present
+/// that is not known.
+pub fn check_unknown_keys() -> Result<(), String> {
+    for (key, _) in env::vars_os() {
+        let Some(key) = key.to_str() else {
+            continue;
+        };
+
+        let is_known =
+            key == KEY_PROFILE || ENV_ONLY_KEYS.contains(&key) || CONFIG_KEYS.contains(&key);
+        if key.starts_with(KEY_PREFIX) && !is_known {
+            return Err(format!("Environment variable '{}' is unknown", key));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn read_profile() -> Result<EnvValue<String>, String> {
+    read_value(&ProcessVars, KEY_PROFILE, "a string", |s| {
+        Ok::<_, ()>(s.to_string())
+    })
+}
+
+pub fn read_mode(vars: &impl Vars) -> Result<EnvValue<Mode>, String> {
     let key = KEY_DEBUG;
-    match env::var(key) {
-        Err(VarError::NotPresent) => read_non_debug_mode(),
-        Err(err) => handle_var_error(key, err),
+    match vars.var(key) {
+        Err(VarError::NotPresent) => read_replay_mode(vars),
+        Err(err) => handle_var_error(vars, key, err),
         Ok(s) => match RunCode::from_str(&s) {
             Ok(run_code) => Ok(EnvValue::Present(Mode::Debug(run_code))),
-            Err(err) => Err(format!("Value for '{}' is not valid: {}", key, err)),
+            Err(err) => Err(format!(
+                "Value for {} is not valid: {}",
+                vars.describe(key),
+                err
+            )),
         },
     }
 }
 
-fn read_non_debug_mode() -> Result<EnvValue<Mode>, String> {
-    match env::var(KEY_MODE) {
-        Err(err) => handle_var_error(KEY_MODE, err),
+fn read_replay_mode(vars: &impl Vars) -> Result<EnvValue<Mode>, String> {
+    let key = KEY_REPLAY;
+    match vars.var(key) {
+        Err(VarError::NotPresent) => read_non_debug_mode(vars),
+        Err(err) => handle_var_error(vars, key, err),
+        Ok(s) => match ReplayCode::from_str(&s) {
+            Ok(replay_code) => Ok(EnvValue::Present(Mode::Replay(replay_code))),
+            Err(err) => Err(format!(
+                "Value for {} is not valid: {}",
+                vars.describe(key),
+                err
+            )),
+        },
+    }
+}
+
+fn read_non_debug_mode(vars: &impl Vars) -> Result<EnvValue<Mode>, String> {
+    match vars.var(KEY_MODE) {
+        Err(err) => handle_var_error(vars, KEY_MODE, err),
         Ok(var) => {
             let str = var.as_str();
             if str == VALUE_REPEATEDLY {
                 Ok(EnvValue::Present(Mode::Repeatedly))
             } else if str == VALUE_ONCE {
                 Ok(EnvValue::Present(Mode::Once))
+            } else if str == VALUE_SAMPLE {
+                Ok(EnvValue::Present(Mode::Sample(DEFAULT_SAMPLES)))
+            } else if let Some(samples) = str
+                .strip_prefix(VALUE_SAMPLE_PREFIX)
+                .and_then(|samples| u64::from_str(samples).ok())
+            {
+                Ok(EnvValue::Present(Mode::Sample(samples)))
             } else {
                 let error = format!(
-                    "Value for '{}' must be either '{}', or '{}'",
-                    KEY_MODE, VALUE_REPEATEDLY, VALUE_ONCE
+                    "Value for {} must be either '{}', '{}', '{}' or '{}<samples>'",
+                    vars.describe(KEY_MODE),
+                    VALUE_REPEATEDLY,
+                    VALUE_ONCE,
+                    VALUE_SAMPLE,
+                    VALUE_SAMPLE_PREFIX,
                 );
                 Err(error)
             }
         }
     }
 }
-pub fn read_regressions_enabled() -> Result<EnvValue<bool>, String> {
-    read_value(KEY_REGRESSIONS_ENABLED, "a bool", bool::from_str)
+
+pub fn read_regressions_enabled(vars: &impl Vars) -> Result<EnvValue<bool>, String> {
+    read_value(vars, KEY_REGRESSIONS_ENABLED, "a bool", bool::from_str)
 }
 
-pub fn read_seed() -> Result<EnvValue<Option<Seed>>, String> {
-    read_option_value(KEY_SEED, "an u64", |s| u64::from_str(s).map(Seed))
+pub fn read_seed(vars: &impl Vars) -> Result<EnvValue<Option<Seed>>, String> {
+    read_option_value(vars, KEY_SEED, "an u64", |s| u64::from_str(s).map(Seed))
 }
 
-pub fn read_once_limit() -> Result<EnvValue<Limit>, String> {
-    read_value(KEY_ONCE_LIMIT, "an u64", |s| u64::from_str(s).map(Limit))
+pub fn read_engine(vars: &impl Vars) -> Result<EnvValue<EngineKind>, String> {
+    let key = KEY_ENGINE;
+    match vars.var(key) {
+        Err(err) => handle_var_error(vars, key, err),
+        Ok(s) => match EngineKind::from_str(&s) {
+            Ok(engine) => Ok(EnvValue::Present(engine)),
+            Err(err) => Err(format!(
+                "Value for {} is not valid: {}",
+                vars.describe(key),
+                err
+            )),
+        },
+    }
 }
 
-pub fn read_start_limit() -> Result<EnvValue<Limit>, String> {
-    read_value(KEY_START_LIMIT, "an u64", |s| u64::from_str(s).map(Limit))
+pub fn read_once_limit(vars: &impl Vars) -> Result<EnvValue<Limit>, String> {
+    read_value(vars, KEY_ONCE_LIMIT, "an u64", |s| {
+        u64::from_str(s).map(Limit)
+    })
 }
 
-pub fn read_end_limit() -> Result<EnvValue<Limit>, String> {
-    read_value(KEY_END_LIMIT, "an u64", |s| u64::from_str(s).map(Limit))
+pub fn read_start_limit(vars: &impl Vars) -> Result<EnvValue<Limit>, String> {
+    read_value(vars, KEY_START_LIMIT, "an u64", |s| {
+        u64::from_str(s).map(Limit)
+    })
 }
 
-pub fn read_limit_multiplier() -> Result<EnvValue<Option<f64>>, String> {
-    read_option_value(KEY_LIMIT_MULTIPLIER, "a f64", f64::from_str)
+pub fn read_end_limit(vars: &impl Vars) -> Result<EnvValue<Limit>, String> {
+    read_value(vars, KEY_END_LIMIT, "an u64", |s| {
+        u64::from_str(s).map(Limit)
+    })
 }
 
-pub fn read_passes() -> Result<EnvValue<u64>, String> {
-    read_value(KEY_PASSES, "an u64", u64::from_str)
+pub fn read_limit_multiplier(vars: &impl Vars) -> Result<EnvValue<Option<f64>>, String> {
+    read_option_value(vars, KEY_LIMIT_MULTIPLIER, "a f64", f64::from_str)
 }
 
-pub fn read_passes_multiplier() -> Result<EnvValue<Option<f64>>, String> {
-    read_option_value(KEY_PASSES_MULTIPLIER, "a f64", f64::from_str)
+pub fn read_passes(vars: &impl Vars) -> Result<EnvValue<u64>, String> {
+    read_value(vars, KEY_PASSES, "an u64", u64::from_str)
 }
 
-pub fn read_hints_enabled() -> Result<EnvValue<bool>, String> {
-    read_value(KEY_HINTS_ENABLED, "a bool", bool::from_str)
+pub fn read_passes_multiplier(vars: &impl Vars) -> Result<EnvValue<Option<f64>>, String> {
+    read_option_value(vars, KEY_PASSES_MULTIPLIER, "a f64", f64::from_str)
 }
 
-pub fn read_stats_enabled() -> Result<EnvValue<bool>, String> {
-    read_value(KEY_STATS_ENABLED, "a bool", bool::from_str)
+pub fn read_hints_enabled(vars: &impl Vars) -> Result<EnvValue<bool>, String> {
+    read_value(vars, KEY_HINTS_ENABLED, "a bool", bool::from_str)
 }
 
-pub fn read_stats_max_value_count() -> Result<EnvValue<Option<usize>>, String> {
-    read_option_value(KEY_STATS_MAX_VALUE_COUNT, "an usize", usize::from_str)
+pub fn read_stats_enabled(vars: &impl Vars) -> Result<EnvValue<bool>, String> {
+    read_value(vars, KEY_STATS_ENABLED, "a bool", bool::from_str)
 }
 
-pub fn read_stats_percent_precision() -> Result<EnvValue<usize>, String> {
-    read_value(KEY_STATS_PERCENT_PRECISION, "an usize", usize::from_str)
+pub fn read_stats_max_value_count(vars: &impl Vars) -> Result<EnvValue<Option<usize>>, String> {
+    read_option_value(vars, KEY_STATS_MAX_VALUE_COUNT, "an usize", usize::from_str)
+}
+
+pub fn read_stats_percent_precision(vars: &impl Vars) -> Result<EnvValue<usize>, String> {
+    read_value(
+        vars,
+        KEY_STATS_PERCENT_PRECISION,
+        "an usize",
+        usize::from_str,
+    )
 }
 
 fn read_value<T, E>(
+    vars: &impl Vars,
     key: &str,
     typ: &str,
     parse: impl FnOnce(&str) -> Result<T, E>,
 ) -> Result<EnvValue<T>, String> {
-    match env::var(key) {
-        Err(err) => handle_var_error(key, err),
+    match vars.var(key) {
+        Err(err) => handle_var_error(vars, key, err),
         Ok(s) => match parse(&s) {
             Ok(value) => Ok(EnvValue::Present(value)),
-            Err(_) => Err(format!("Value for '{}' must be {}", key, typ)),
+            Err(_) => Err(format!(
+                "Value '{}' for {} must be {}",
+                s,
+                vars.describe(key),
+                typ
+            )),
         },
     }
 }
 
 fn read_option_value<T, E>(
+    vars: &impl Vars,
     key: &str,
     typ: &str,
     parse: impl FnOnce(&str) -> Result<T, E>,
 ) -> Result<EnvValue<Option<T>>, String> {
-    match env::var(key) {
-        Err(err) => handle_var_error(key, err),
+    match vars.var(key) {
+        Err(err) => handle_var_error(vars, key, err),
         Ok(s) if s == VALUE_NONE => Ok(EnvValue::Present(None)),
         Ok(s) => match parse(&s) {
             Ok(value) => Ok(EnvValue::Present(Some(value))),
             Err(_) => Err(format!(
-                "Value for '{}' must be either '{}' or {}",
-                key, VALUE_NONE, typ
+                "Value '{}' for {} must be either '{}' or {}",
+                s,
+                vars.describe(key),
+                VALUE_NONE,
+                typ
             )),
         },
     }
 }
 
-fn handle_var_error<T>(key: &str, err: VarError) -> Result<EnvValue<T>, String> {
+fn handle_var_error<T>(vars: &impl Vars, key: &str, err: VarError) -> Result<EnvValue<T>, String> {
     match err {
         VarError::NotPresent => Ok(EnvValue::NotPresent),
-        VarError::NotUnicode(_) => Err(format!("Value for '{}' is not valid unicode", key)),
+        VarError::NotUnicode(_) => Err(format!(
+            "Value for {} is not valid unicode",
+            vars.describe(key)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::frontend::Mode;
+    use crate::frontend::env::{EnvValue, SectionVars, read_mode};
+
+    fn read_mode_from(value: &str) -> Result<Option<Mode>, String> {
+        let values = BTreeMap::from([("DICETEST_MODE", value.to_string())]);
+        let vars = SectionVars {
+            location: "test",
+            values: &values,
+        };
+        read_mode(&vars).map(|mode| match mode {
+            EnvValue::NotPresent => None,
+            EnvValue::Present(mode) => Some(mode),
+        })
+    }
+
+    #[test]
+    fn read_mode_sample_uses_default_samples() {
+        assert!(matches!(
+            read_mode_from("sample"),
+            Ok(Some(Mode::Sample(10)))
+        ));
+    }
+
+    #[test]
+    fn read_mode_sample_with_samples() {
+        assert!(matches!(
+            read_mode_from("sample:42"),
+            Ok(Some(Mode::Sample(42)))
+        ));
+        assert!(matches!(
+            read_mode_from("sample:0"),
+            Ok(Some(Mode::Sample(0)))
+        ));
+    }
+
+    #[test]
+    fn read_mode_sample_with_invalid_samples_is_error() {
+        assert!(read_mode_from("sample:").is_err());
+        assert!(read_mode_from("sample:-1").is_err());
+        assert!(read_mode_from("sample:many").is_err());
     }
 }
@@ -1,9 +1,10 @@
 use std::fmt::{self, Display};
 use std::iter::FromIterator;
 
-use crate::frontend::RunCode;
+use crate::frontend::{ReplayCode, RunCode};
 use crate::hints::Hints;
 use crate::runner::repeatedly::Regression;
+use crate::runner::sample::Sample;
 use crate::runner::{self, Error, repeatedly::Counterexample};
 use crate::stats::Stats;
 use crate::{Limit, Seed};
@@ -40,6 +41,7 @@ pub fn display_run_once_report<'a>(
     seed: Option<Seed>,
     report: &'a runner::once::Report,
     formatting: &'a Formatting,
+    warnings: &'a [String],
 ) -> impl Display + 'a {
     impl_display(move |f| {
         let passed = report.error.is_none();
@@ -53,6 +55,11 @@ pub fn display_run_once_report<'a>(
             write_stats_section(f, stats, formatting)?;
         }
 
+        if !warnings.is_empty() {
+            writeln!(f)?;
+            write_warnings_section(f, warnings)?;
+        }
+
         Ok(())
     })
 }
@@ -62,6 +69,7 @@ pub fn display_run_repeatedly_report<'a>(
     config: &'a runner::repeatedly::Config,
     report: &'a runner::repeatedly::Report,
     formatting: &'a Formatting,
+    warnings: &'a [String],
 ) -> impl Display + 'a {
     impl_display(move |f| {
         let counterexample = &report.counterexample;
@@ -80,9 +88,36 @@ pub fn display_run_repeatedly_report<'a>(
             let hints_enabled = cfg!(feature = "hints") && config.hints_enabled;
 
             writeln!(f)?;
-            write_counterexample_section(f, hints_enabled, counterexample)?;
+            write_counterexample_section(f, seed, hints_enabled, counterexample)?;
         };
 
+        if !warnings.is_empty() {
+            writeln!(f)?;
+            write_warnings_section(f, warnings)?;
+        }
+
+        Ok(())
+    })
+}
+
+pub fn display_sample_report<'a>(
+    seed: Seed,
+    config: &'a runner::sample::Config,
+    report: &'a runner::sample::Report,
+    formatting: &'a Formatting,
+) -> impl Display + 'a {
+    impl_display(move |f| {
+        write!(f, "The test was sampled {} times.", report.samples.len())?;
+        write!(f, "\n\n")?;
+        write_sample_parameters_section(f, seed, config)?;
+        writeln!(f)?;
+        write_samples_section(f, &report.samples)?;
+
+        if let Some(ref stats) = report.stats {
+            writeln!(f)?;
+            write_histograms_section(f, stats, formatting)?;
+        }
+
         Ok(())
     })
 }
@@ -226,8 +261,231 @@ fn write_stats_section(
     )
 }
 
+fn write_sample_parameters_section(
+    f: &mut fmt::Formatter,
+    seed: Seed,
+    config: &runner::sample::Config,
+) -> fmt::Result {
+    write_section(
+        f,
+        "Config",
+        impl_display(|f| {
+            write_seed_item(f, 0, seed)?;
+            write_key_value_item(f, 0, "start limit", config.start_limit.0)?;
+            write_key_value_item(f, 0, "end limit", config.end_limit.0)?;
+            write_key_value_item(f, 0, "samples", config.samples)
+        }),
+    )
+}
+
+fn write_samples_section(f: &mut fmt::Formatter, samples: &[Sample]) -> fmt::Result {
+    write_section(
+        f,
+        "Samples",
+        impl_display(|f| {
+            for (index, sample) in samples.iter().enumerate() {
+                write_key_item(f, 0, impl_display(|f| write!(f, "sample {}", index)))?;
+                write_limit_item(f, 1, sample.limit)?;
+                if let Some(ref hints) = sample.hints {
+                    write_hints_item(f, 1, hints)?;
+                }
+                if let Some(ref error) = sample.error {
+                    write_error_item(f, 1, error)?;
+                }
+            }
+            Ok(())
+        }),
+    )
+}
+
+// The number of characters used for the longest bar of a histogram.
+const HISTOGRAM_WIDTH: u64 = 40;
+
+fn write_histograms_section(
+    f: &mut fmt::Formatter,
+    stats: &Stats,
+    formatting: &Formatting,
+) -> fmt::Result {
+    write_section(
+        f,
+        "Histograms",
+        impl_display(|f| {
+            if stats.0.is_empty() {
+                write_item(f, 0, "No stats has been collected.")
+            } else {
+                for (key, stat) in &stats.0 {
+                    let mut values = stat
+                        .0
+                        .iter()
+                        .map(|(value, counter)| {
+                            (value.clone(), counter.value().unwrap_or(u64::MAX))
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Numeric values are ordered by value and grouped into ranges if there are
+                    // too many, categorical values are ordered by their count
+                    let numbers = values
+                        .iter()
+                        .map(|(value, _)| value.parse::<f64>().ok())
+                        .collect::<Option<Vec<_>>>();
+                    let mut omitted_value_count = 0;
+                    match numbers {
+                        Some(numbers) => {
+                            let mut numbered_values = numbers
+                                .into_iter()
+                                .zip(values)
+                                .map(|(number, (value, count))| (number, value, count))
+                                .collect::<Vec<_>>();
+                            numbered_values.sort_by(|(n1, _, _), (n2, _, _)| n1.total_cmp(n2));
+                            values = match formatting.stats_max_value_count {
+                                Some(bin_count)
+                                    if numbered_values.len() > bin_count
+                                        && numbered_values
+                                            .iter()
+                                            .all(|(n, _, _)| n.is_finite()) =>
+                                {
+                                    bin_numeric_values(&numbered_values, bin_count)
+                                }
+                                _ => numbered_values
+                                    .into_iter()
+                                    .map(|(_, value, count)| (value, count))
+                                    .collect(),
+                            };
+                        }
+                        None => {
+                            values.sort_by(|&(_, c1), &(_, c2)| c1.cmp(&c2).reverse());
+                            if let Some(max_value_count) = formatting.stats_max_value_count {
+                                omitted_value_count = values.len().saturating_sub(max_value_count);
+                                values.truncate(max_value_count);
+                            }
+                        }
+                    }
+
+                    let total = stat.total_counter().value().filter(|&n| n != 0);
+                    let max_count = values.iter().map(|&(_, count)| count).max().unwrap_or(0);
+                    let value_width = values
+                        .iter()
+                        .map(|(value, _)| value.chars().count())
+                        .max()
+                        .unwrap_or(0);
+
+                    write_key_item(f, 0, key)?;
+
+                    for (value, count) in &values {
+                        let count = *count;
+                        let bar_len = if max_count == 0 {
+                            0
+                        } else {
+                            (u128::from(count) * u128::from(HISTOGRAM_WIDTH))
+                                .div_ceil(u128::from(max_count))
+                                as usize
+                        };
+                        let percent = total.map(|total| count as f64 * 100.0 / total as f64);
+                        write_item(
+                            f,
+                            1,
+                            impl_display(|f| {
+                                write!(
+                                    f,
+                                    "{:<value_width$} | {:<bar_width$} ",
+                                    value,
+                                    "#".repeat(bar_len),
+                                    value_width = value_width,
+                                    bar_width = HISTOGRAM_WIDTH as usize,
+                                )?;
+                                match percent {
+                                    None => write!(f, "ovf")?,
+                                    Some(percent) => {
+                                        let percent_precision = formatting.stats_percent_precision;
+                                        write!(f, "{:.n$}", percent, n = percent_precision)?
+                                    }
+                                };
+                                write!(f, "% ({})", count)
+                            }),
+                        )?;
+                    }
+
+                    if omitted_value_count != 0 {
+                        write_item(
+                            f,
+                            1,
+                            impl_display(|f| {
+                                write!(f, "{} values were omitted", omitted_value_count)
+                            }),
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+        }),
+    )
+}
+
+// Groups the numeric values sorted by number into at most the given number of ranges with equal
+// width. Integers are grouped into inclusive integer ranges.
+fn bin_numeric_values(values: &[(f64, String, u64)], bin_count: usize) -> Vec<(String, u64)> {
+    let (Some((min, _, _)), Some((max, _, _))) = (values.first(), values.last()) else {
+        return Vec::new();
+    };
+    if bin_count == 0 {
+        return Vec::new();
+    }
+
+    let are_integers = values
+        .iter()
+        .all(|(number, _, _)| number.fract() == 0.0 && number.abs() < 2f64.powi(63));
+
+    if are_integers {
+        let (min, max) = (*min as i128, *max as i128);
+        let bin_count = bin_count as i128;
+        let width = (max - min + bin_count) / bin_count;
+        let mut bins = Vec::new();
+        let mut lower = min;
+        while lower <= max {
+            let upper = (lower + width - 1).min(max);
+            let count = values
+                .iter()
+                .filter(|(number, _, _)| (lower..=upper).contains(&(*number as i128)))
+                .fold(0u64, |sum, (_, _, count)| sum.saturating_add(*count));
+            let label = if lower == upper {
+                format!("{lower}")
+            } else {
+                format!("{lower}..={upper}")
+            };
+            bins.push((label, count));
+            lower = upper + 1;
+        }
+        bins
+    } else {
+        let width = (max - min) / bin_count as f64;
+        let mut counts = vec![0u64; bin_count];
+        for (number, _, count) in values {
+            let index = if width > 0.0 {
+                (((number - min) / width) as usize).min(bin_count - 1)
+            } else {
+                0
+            };
+            counts[index] = counts[index].saturating_add(*count);
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(index, count)| {
+                let lower = min + index as f64 * width;
+                let label = if index + 1 == bin_count {
+                    format!("{lower}..={max}")
+                } else {
+                    format!("{lower}..{}", min + (index + 1) as f64 * width)
+                };
+                (label, count)
+            })
+            .collect()
+    }
+}
+
 fn write_counterexample_section(
     f: &mut fmt::Formatter,
+    seed: Seed,
     hints_enabled: bool,
     counterexample: &Counterexample,
 ) -> fmt::Result {
@@ -235,11 +493,14 @@ fn write_counterexample_section(
         f,
         "Counterexample",
         impl_display(|f| {
-            let run_code = RunCode {
-                prng: counterexample.prng.clone(),
-                limit: counterexample.limit,
-            };
+            let run_code = RunCode::new(counterexample.prng.clone(), counterexample.limit);
             write_run_code_item(f, 0, &run_code)?;
+
+            if let Some(pass) = counterexample.pass {
+                let replay_code = ReplayCode { seed, pass };
+                write_key_value_item(f, 0, "replay code", replay_code)?;
+            }
+
             write_limit_item(f, 0, counterexample.limit)?;
 
             match counterexample.hints {
@@ -257,6 +518,19 @@ fn write_counterexample_section(
     )
 }
 
+fn write_warnings_section(f: &mut fmt::Formatter, warnings: &[String]) -> fmt::Result {
+    write_section(
+        f,
+        "Warnings",
+        impl_display(|f| {
+            for warning in warnings {
+                write_item(f, 0, warning)?;
+            }
+            Ok(())
+        }),
+    )
+}
+
 fn write_section(
     f: &mut fmt::Formatter,
     title: &'static str,
@@ -283,10 +557,7 @@ fn write_regressions_item(
     if !regressions.is_empty() {
         write_key_item(f, indent, "regressions")?;
         for regression in regressions {
-            let run_code = RunCode {
-                prng: regression.prng.clone(),
-                limit: regression.limit,
-            };
+            let run_code = RunCode::new(regression.prng.clone(), regression.limit);
             write_item(f, indent + 1, impl_display(|f| write!(f, "{run_code}")))?;
         }
     }
@@ -402,10 +673,7 @@ mod tests {
     }
 
     fn example_run_code(seed: u64) -> RunCode {
-        RunCode {
-            prng: Prng::from_seed(seed.into()),
-            limit: Limit(71),
-        }
+        RunCode::new(Prng::from_seed(seed.into()), Limit(71))
     }
 
     fn example_error() -> Error {
@@ -449,7 +717,39 @@ The test passed.
 
         let actual = format!(
             "{}",
-            display_run_once_report(&run_code, None, &report, &formatting,)
+            display_run_once_report(&run_code, None, &report, &formatting, &[])
+        );
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn display_run_once_report_passed_with_warnings_example() {
+        let run_code = example_run_code(42);
+        let report = runner::once::Report {
+            hints: None,
+            stats: None,
+            error: None,
+        };
+        let formatting = Formatting::default();
+        let warnings = vec!["Be careful!".to_string()];
+
+        let expected = format!(
+            "\
+The test passed.
+
+# Run
+- run code: {run_code}
+- limit: 71
+
+# Warnings
+- Be careful!
+"
+        );
+
+        let actual = format!(
+            "{}",
+            display_run_once_report(&run_code, None, &report, &formatting, &warnings)
         );
 
         assert_eq!(expected, actual);
@@ -478,7 +778,7 @@ The test passed.
 
         let actual = format!(
             "{}",
-            display_run_once_report(&run_code, Some(42.into()), &report, &formatting,)
+            display_run_once_report(&run_code, Some(42.into()), &report, &formatting, &[])
         );
 
         assert_eq!(expected, actual);
@@ -526,7 +826,7 @@ The test passed.
 
         let actual = format!(
             "{}",
-            display_run_once_report(&run_code, None, &report, &formatting,)
+            display_run_once_report(&run_code, None, &report, &formatting, &[])
         );
 
         assert_eq!(expected, actual);
@@ -559,7 +859,7 @@ The test failed.
 
         let actual = format!(
             "{}",
-            display_run_once_report(&run_code, None, &report, &formatting,)
+            display_run_once_report(&run_code, None, &report, &formatting, &[])
         );
 
         assert_eq!(expected, actual);
@@ -588,7 +888,7 @@ The test withstood 200 passes.
 
         let actual = format!(
             "{}",
-            display_run_repeatedly_report(seed, &config, &report, &formatting,)
+            display_run_repeatedly_report(seed, &config, &report, &formatting, &[])
         );
 
         assert_eq!(expected, actual);
@@ -634,7 +934,7 @@ The test withstood 200 passes.
 
         let actual = format!(
             "{}",
-            display_run_repeatedly_report(seed, &config, &report, &formatting,)
+            display_run_repeatedly_report(seed, &config, &report, &formatting, &[])
         );
 
         assert_eq!(expected, actual);
@@ -651,6 +951,7 @@ The test withstood 200 passes.
             counterexample: Some(Counterexample {
                 prng: run_code.prng.clone(),
                 limit: run_code.limit,
+                pass: Some(123),
                 hints: Some(example_hints()),
                 error: example_error(),
             }),
@@ -669,6 +970,7 @@ The test failed after 123 passes.
 
 # Counterexample
 - run code: {run_code}
+- replay code: 42:123
 - limit: 71
 - hints:
 \t- Uh
@@ -680,7 +982,7 @@ The test failed after 123 passes.
 
         let actual = format!(
             "{}",
-            display_run_repeatedly_report(seed, &config, &report, &formatting,)
+            display_run_repeatedly_report(seed, &config, &report, &formatting, &[])
         );
 
         assert_eq!(expected, actual);
@@ -697,6 +999,7 @@ The test failed after 123 passes.
                 counterexample: Some(Counterexample {
                     prng: Prng::from_seed(42.into()),
                     limit: Limit(71),
+                    pass: Some(123),
                     hints: None,
                     error: Error(Box::new("Something bad happened!")),
                 }),
@@ -705,7 +1008,7 @@ The test failed after 123 passes.
 
             let actual = format!(
                 "{}",
-                display_run_repeatedly_report(seed, &config, &report, &formatting,)
+                display_run_repeatedly_report(seed, &config, &report, &formatting, &[])
             );
 
             assert!(contains_line(
@@ -729,6 +1032,7 @@ The test failed after 123 passes.
                 counterexample: Some(Counterexample {
                     prng: Prng::from_seed(42.into()),
                     limit: Limit(71),
+                    pass: Some(123),
                     hints: Some(Hints::new()),
                     error: Error(Box::new("Something bad happened!")),
                 }),
@@ -737,7 +1041,7 @@ The test failed after 123 passes.
 
             let actual = format!(
                 "{}",
-                display_run_repeatedly_report(seed, &config, &report, &formatting,)
+                display_run_repeatedly_report(seed, &config, &report, &formatting, &[])
             );
 
             assert!(contains_line(&actual, "- No hints has been collected.",));
@@ -839,10 +1143,206 @@ The test failed after 123 passes.
 
             let actual = format!(
                 "{}",
-                display_run_repeatedly_report(seed, &config, &report, &formatting,)
+                display_run_repeatedly_report(seed, &config, &report, &formatting, &[])
             );
 
             assert!(contains_line(&actual, "- No stats has been collected.",));
         }
     }
+
+    #[test]
+    fn display_sample_report_example() {
+        let config = runner::sample::Config {
+            start_limit: 0.into(),
+            end_limit: 100.into(),
+            samples: 2,
+        };
+        let report = runner::sample::Report {
+            samples: vec![
+                Sample {
+                    limit: 0.into(),
+                    hints: Some(example_hints()),
+                    error: None,
+                },
+                Sample {
+                    limit: 100.into(),
+                    hints: Some(Hints(Vec::new())),
+                    error: Some(example_error()),
+                },
+            ],
+            stats: None,
+        };
+        let formatting = Formatting::default();
+
+        let expected = "\
+The test was sampled 2 times.
+
+# Config
+- seed: 42
+- start limit: 0
+- end limit: 100
+- samples: 2
+
+# Samples
+- sample 0:
+\t- limit: 0
+\t- hints:
+\t\t- Uh
+\t\t\t- Ah
+\t\t- Ih
+- sample 1:
+\t- limit: 100
+\t- No hints has been collected.
+\t- error: Something bad happened!
+";
+
+        let actual = format!(
+            "{}",
+            display_sample_report(42.into(), &config, &report, &formatting)
+        );
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn histograms_section_groups_too_many_integers() {
+        let stats = Stats(
+            vec![(
+                "number",
+                Stat(
+                    (1..=10)
+                        .map(|n| (n.to_string(), Counter::Value(1)))
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let formatting = Formatting {
+            stats_max_value_count: Some(3),
+            stats_percent_precision: 1,
+        };
+        let bar = |len: usize| format!("{:<40}", "#".repeat(len));
+
+        let expected = format!(
+            "\
+# Histograms
+- number:
+\t- 1..=4  | {bar_40} 40.0% (4)
+\t- 5..=8  | {bar_40} 40.0% (4)
+\t- 9..=10 | {bar_20} 20.0% (2)
+",
+            bar_40 = bar(40),
+            bar_20 = bar(20),
+        );
+
+        let actual = format!(
+            "{}",
+            impl_display(|f| write_histograms_section(f, &stats, &formatting))
+        );
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn histograms_section_groups_too_many_floats() {
+        let stats = Stats(
+            vec![(
+                "number",
+                Stat(
+                    ["0", "0.5", "1", "1.5", "2"]
+                        .into_iter()
+                        .map(|n| (n.into(), Counter::Value(1)))
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let formatting = Formatting {
+            stats_max_value_count: Some(2),
+            stats_percent_precision: 1,
+        };
+        let bar = |len: usize| format!("{:<40}", "#".repeat(len));
+
+        let expected = format!(
+            "\
+# Histograms
+- number:
+\t- 0..1  | {bar_27} 40.0% (2)
+\t- 1..=2 | {bar_40} 60.0% (3)
+",
+            bar_40 = bar(40),
+            bar_27 = bar(27),
+        );
+
+        let actual = format!(
+            "{}",
+            impl_display(|f| write_histograms_section(f, &stats, &formatting))
+        );
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn histograms_section_example() {
+        let stats = Stats(
+            vec![
+                (
+                    "number",
+                    Stat(
+                        vec![
+                            ("10".into(), Counter::Value(1)),
+                            ("2".into(), Counter::Value(4)),
+                            ("-1".into(), Counter::Value(3)),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                ),
+                (
+                    "category",
+                    Stat(
+                        vec![
+                            ("a".into(), Counter::Value(1)),
+                            ("bb".into(), Counter::Value(3)),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let formatting = Formatting {
+            stats_max_value_count: None,
+            stats_percent_precision: 1,
+        };
+        let bar = |len: usize| format!("{:<40}", "#".repeat(len));
+
+        let expected = format!(
+            "\
+# Histograms
+- category:
+\t- bb | {bar_40} 75.0% (3)
+\t- a  | {bar_14} 25.0% (1)
+- number:
+\t- -1 | {bar_30} 37.5% (3)
+\t- 2  | {bar_40} 50.0% (4)
+\t- 10 | {bar_10} 12.5% (1)
+",
+            bar_40 = bar(40),
+            bar_30 = bar(30),
+            bar_14 = bar(14),
+            bar_10 = bar(10),
+        );
+
+        let actual = format!(
+            "{}",
+            impl_display(|f| write_histograms_section(f, &stats, &formatting))
+        );
+
+        assert_eq!(expected, actual);
+    }
 }
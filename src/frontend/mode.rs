@@ -1,8 +1,10 @@
-use crate::frontend::RunCode;
+use crate::frontend::{ReplayCode, RunCode};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Mode {
     Debug(RunCode),
+    Replay(ReplayCode),
     Once,
     Repeatedly,
+    Sample(u64),
 }
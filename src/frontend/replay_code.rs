@@ -0,0 +1,80 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::Seed;
+
+/// Identifies a random test run of the run-repeatedly mode by its seed and the index of the pass.
+///
+/// In contrast to a run code, it's only valid in combination with the limits and the number of
+/// passes of the test run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayCode {
+    pub seed: Seed,
+    pub pass: u64,
+}
+
+impl FromStr for ReplayCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (seed, pass) = s
+            .split_once(':')
+            .ok_or_else(|| "Replay code must have the format '<seed>:<pass>'".to_string())?;
+
+        let seed = u64::from_str(seed)
+            .map_err(|_| "Seed of replay code must be an u64".to_string())?
+            .into();
+        let pass =
+            u64::from_str(pass).map_err(|_| "Pass of replay code must be an u64".to_string())?;
+
+        Ok(ReplayCode { seed, pass })
+    }
+}
+
+impl Display for ReplayCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.seed.0, self.pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::frontend::ReplayCode;
+    use crate::laws;
+    use crate::prelude::*;
+
+    #[test]
+    fn to_string_is_right_inverse_for_from_str() {
+        Dicetest::repeatedly().run(|fate| {
+            let replay_code_die =
+                dice::zip()
+                    .two(dice::u64(..), dice::u64(..))
+                    .map(|(seed, pass)| ReplayCode {
+                        seed: seed.into(),
+                        pass,
+                    });
+            laws::right_inverse(
+                fate,
+                replay_code_die,
+                |code: String| ReplayCode::from_str(&code).unwrap(),
+                |replay_code| replay_code.to_string(),
+            );
+        })
+    }
+
+    #[test]
+    fn from_str_examples() {
+        assert_eq!(
+            ReplayCode::from_str("42:7"),
+            Ok(ReplayCode {
+                seed: 42.into(),
+                pass: 7
+            })
+        );
+        assert!(ReplayCode::from_str("42").is_err());
+        assert!(ReplayCode::from_str("42:").is_err());
+        assert!(ReplayCode::from_str("x:7").is_err());
+    }
+}
@@ -1,38 +1,155 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::engine::EngineKind;
 use crate::util::{base62, conversion};
 use crate::{Limit, Prng};
 
+// The tag of the current run code format. It's the first byte of the run code.
+//
+// The format consists of the format tag, the version, the engine, the `Prng` state, the `Limit`
+// and the checksum.
+const FORMAT_TAG: u8 = 2;
+
+// Run codes created before the format was versioned consist only of the `Prng` and the `Limit`.
+const LEGACY_LEN: usize = 40;
+
+// The format tag, the version and the engine.
+const HEADER_LEN: usize = 5;
+const LIMIT_LEN: usize = 8;
+const CHECKSUM_LEN: usize = 4;
+
+/// The version of dicetest that created a run code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+    pub patch: u8,
+}
+
+impl Version {
+    /// Returns the version of this crate.
+    pub fn current() -> Self {
+        fn parse(s: &str) -> u8 {
+            s.parse::<u64>().map_or(u8::MAX, |n| n.min(255) as u8)
+        }
+
+        Version {
+            major: parse(env!("CARGO_PKG_VERSION_MAJOR")),
+            minor: parse(env!("CARGO_PKG_VERSION_MINOR")),
+            patch: parse(env!("CARGO_PKG_VERSION_PATCH")),
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RunCode {
     pub prng: Prng,
     pub limit: Limit,
+    /// The version of dicetest that created the run code or `None` if the run code was
+    /// created before run codes were versioned.
+    pub version: Option<Version>,
+}
+
+impl RunCode {
+    /// Creates a run code for the current version of this crate.
+    pub fn new(prng: Prng, limit: Limit) -> Self {
+        RunCode {
+            prng,
+            limit,
+            version: Some(Version::current()),
+        }
+    }
+
+    /// Returns a warning if the run code was created by a different version of dicetest.
+    pub fn version_warning(&self) -> Option<String> {
+        let current = Version::current();
+
+        let recorded = match self.version {
+            Some(version) if version == current => return None,
+            Some(version) => format!("dicetest {}", version),
+            None => "an unknown older version of dicetest".to_string(),
+        };
+
+        Some(format!(
+            "Run code {} was created by {}, but this is dicetest {}. \
+            The generators may have changed since the run code was recorded, \
+            hence the test might not reproduce the original run.",
+            self, recorded, current
+        ))
+    }
 }
 
 impl FromStr for RunCode {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = base62::decode(s)?;
+        let bytes = base62::decode(s).map_err(|err| format!("Run code is invalid: {err}"))?;
 
-        if bytes.len() != 40 {
-            return Err("Run code has invalid length".to_string());
+        if bytes.len() == LEGACY_LEN {
+            let prng = prng_from_bytes(EngineKind::Jsf64, &bytes[0..32])?;
+            let limit = limit_from_bytes(&bytes[32..40]);
+
+            return Ok(RunCode {
+                prng,
+                limit,
+                version: None,
+            });
         }
 
-        let prng = {
-            let mut seed_bytes = [0; 32];
-            seed_bytes.copy_from_slice(&bytes[0..32]);
-            Prng::from_bytes(seed_bytes)
-        };
+        match bytes.first() {
+            None => return Err("Run code is empty".to_string()),
+            Some(&FORMAT_TAG) => (),
+            Some(&tag) if tag > FORMAT_TAG => {
+                return Err(format!(
+                    "Run code has the unknown format {tag}, \
+                    it was probably created by a newer version of dicetest"
+                ));
+            }
+            Some(_) => return Err("Run code is corrupted, it has an invalid format".to_string()),
+        }
+
+        if bytes.len() <= HEADER_LEN + LIMIT_LEN + CHECKSUM_LEN {
+            return Err("Run code is corrupted, it has an invalid length".to_string());
+        }
+
+        let (payload, expected_checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+
+        if checksum(payload) != expected_checksum {
+            return Err(
+                "Run code is corrupted, its checksum doesn't match. Please check it for typos"
+                    .to_string(),
+            );
+        }
 
-        let limit = {
-            let mut limit_bytes = [0; 8];
-            limit_bytes.copy_from_slice(&bytes[32..40]);
-            Limit(conversion::bytes_to_u64(limit_bytes))
+        let version = Version {
+            major: payload[1],
+            minor: payload[2],
+            patch: payload[3],
         };
+        let engine_kind = EngineKind::from_id(payload[4]).ok_or_else(|| {
+            format!(
+                "Run code uses the unknown engine {}, \
+                it was probably created by a newer version of dicetest",
+                payload[4]
+            )
+        })?;
+        let (prng_bytes, limit_bytes) =
+            payload[HEADER_LEN..].split_at(payload.len() - HEADER_LEN - LIMIT_LEN);
+        let prng = prng_from_bytes(engine_kind, prng_bytes)?;
+        let limit = limit_from_bytes(limit_bytes);
 
-        let run_code = RunCode { prng, limit };
+        let run_code = RunCode {
+            prng,
+            limit,
+            version: Some(version),
+        };
 
         Ok(run_code)
     }
@@ -42,8 +159,28 @@ impl Display for RunCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut bytes = Vec::new();
 
-        bytes.extend_from_slice(&self.prng.to_bytes());
-        bytes.extend_from_slice(&conversion::u64_to_bytes(self.limit.0));
+        match self.version {
+            None if self.prng.engine_kind() == EngineKind::Jsf64 => {
+                bytes.extend_from_slice(&self.prng.to_bytes());
+                bytes.extend_from_slice(&conversion::u64_to_bytes(self.limit.0));
+            }
+            version => {
+                // The legacy format doesn't support other engines, hence we use the current
+                // format with an unknown version.
+                let version = version.unwrap_or(Version {
+                    major: 0,
+                    minor: 0,
+                    patch: 0,
+                });
+                bytes.push(FORMAT_TAG);
+                bytes.extend_from_slice(&[version.major, version.minor, version.patch]);
+                bytes.push(self.prng.engine_kind().id());
+                bytes.extend_from_slice(&self.prng.to_bytes());
+                bytes.extend_from_slice(&conversion::u64_to_bytes(self.limit.0));
+                let checksum = checksum(&bytes);
+                bytes.extend_from_slice(&checksum);
+            }
+        }
 
         let string = base62::encode(&bytes);
 
@@ -51,29 +188,113 @@ impl Display for RunCode {
     }
 }
 
+fn prng_from_bytes(engine_kind: EngineKind, bytes: &[u8]) -> Result<Prng, String> {
+    Prng::from_bytes(engine_kind, bytes).ok_or_else(|| {
+        format!("Run code is corrupted, it has an invalid state for engine {engine_kind}")
+    })
+}
+
+fn limit_from_bytes(bytes: &[u8]) -> Limit {
+    let mut limit_bytes = [0; 8];
+    limit_bytes.copy_from_slice(bytes);
+    Limit(conversion::bytes_to_u64(limit_bytes))
+}
+
+// Calculates the 32-bit FNV-1a hash of the bytes. It's used for detecting typos.
+fn checksum(bytes: &[u8]) -> [u8; 4] {
+    let mut hash: u32 = 0x811c_9dc5;
+    for &byte in bytes {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash.to_le_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
+    use crate::engine::EngineKind;
     use crate::frontend::RunCode;
+    use crate::frontend::run_code::Version;
     use crate::prelude::*;
-    use crate::{Limit, asserts};
+    use crate::util::base62;
+    use crate::{Limit, Prng, laws};
+
+    fn run_code_die() -> impl Die<RunCode> {
+        let prng_die = dice::zip()
+            .two(dice::one_of_slice(&EngineKind::ALL), dice::u64(..))
+            .map(|(kind, seed)| Prng::from_engine_seed(kind, seed.into()));
+        let limit_die = dice::u64(..).map(Limit);
+        dice::zip()
+            .two(prng_die, limit_die)
+            .map(|(prng, limit)| RunCode::new(prng, limit))
+    }
 
     #[test]
     fn to_string_is_right_inverse_for_from_str() {
         Dicetest::repeatedly().run(|fate| {
-            let prng_die = dice::from_fn(|mut fate| fate.fork_prng());
-            let limit_die = dice::u64(..).map(Limit);
-            let run_code_die = dice::zip()
-                .two(prng_die, limit_die)
-                .map(|(prng, limit)| RunCode { prng, limit });
-
-            asserts::right_inverse(
+            laws::right_inverse(
                 fate,
-                run_code_die,
+                run_code_die(),
                 |base32: String| RunCode::from_str(&base32).unwrap(),
                 |run_code| run_code.to_string(),
             );
         })
     }
+
+    #[test]
+    fn from_str_detects_typo() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let run_code = fate.roll(run_code_die()).to_string();
+            let index = fate.roll(dice::uni_usize(1..run_code.len()));
+            let old_char = run_code.as_bytes()[index] as char;
+            let new_char = fate.roll(dice::char_ascii_alphanumeric());
+
+            if old_char == new_char {
+                return;
+            }
+
+            let mut corrupted_run_code = run_code.clone();
+            corrupted_run_code.replace_range(index..index + 1, &new_char.to_string());
+
+            hint_debug!(run_code);
+            hint_debug!(corrupted_run_code);
+
+            assert!(RunCode::from_str(&corrupted_run_code).is_err());
+        })
+    }
+
+    #[test]
+    fn from_str_accepts_legacy_run_code() {
+        let run_code =
+            RunCode::from_str("3lTBtDxQx6SneW3r4sNLUVoYAREJ8OuO9B0yp31nna0NdwFGFvA4no").unwrap();
+        assert_eq!(run_code.version, None);
+        assert!(run_code.version_warning().is_some());
+    }
+
+    #[test]
+    fn version_warning_examples() {
+        let current = RunCode::new(Prng::from_seed(42.into()), Limit(42));
+        assert_eq!(current.version_warning(), None);
+
+        let old = RunCode {
+            version: Some(Version {
+                major: 0,
+                minor: 1,
+                patch: 0,
+            }),
+            ..current
+        };
+        let old = RunCode::from_str(&old.to_string()).unwrap();
+        assert!(old.version_warning().unwrap().contains("dicetest 0.1.0"));
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_format() {
+        let mut bytes = vec![3];
+        bytes.extend_from_slice(&[0; 48]);
+        let result = RunCode::from_str(&base62::encode(&bytes));
+        assert!(result.unwrap_err().contains("newer version"));
+    }
 }
@@ -0,0 +1,783 @@
+//! Ready-made checks for common algebraic laws.
+//!
+//! Each check takes a [`Fate`] and the generators for its inputs. It panics if the law is
+//! violated. The generated values are added as hints, hence the counterexample shows the
+//! values that violate the law.
+//!
+//! # Example
+//!
+//! ```
+//! use dicetest::prelude::*;
+//! use dicetest::laws;
+//!
+//! Dicetest::repeatedly().run(|fate| {
+//!     laws::semigroup_associative(fate, dice::u8(..), |x, y| x.max(y));
+//! });
+//! ```
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use crate::prelude::*;
+
+// Generates either a clone of the given value or an independent value. Laws that have a
+// precondition of equality are hard to check with independent values only.
+fn roll_maybe_equal<T: Clone>(fate: &mut Fate, die: impl Die<T>, value: &T) -> T {
+    if fate.roll(dice::bool()) {
+        value.clone()
+    } else {
+        fate.roll(die)
+    }
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Checks that `g` is a left inverse for `f`, i.e. `g(f(x)) == x`.
+///
+/// This is useful for round trips like deserializing a serialized value.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::left_inverse(
+///         fate,
+///         dice::u32(..),
+///         |x| x.to_string(),
+///         |s| s.parse::<u32>().unwrap(),
+///     );
+/// });
+/// ```
+pub fn left_inverse<X, Y>(
+    mut fate: Fate,
+    x_die: impl DieOnce<X>,
+    f: impl FnOnce(X) -> Y,
+    g: impl FnOnce(Y) -> X,
+) where
+    X: Debug + Clone + PartialEq,
+{
+    let x = fate.roll(x_die);
+    hint_debug!(x);
+    let y = f(x.clone());
+    let other_x = g(y);
+    hint_debug!(other_x);
+
+    assert_eq!(x, other_x, "left inverse law is violated")
+}
+
+/// Checks that `h` is a right inverse for `f`, i.e. `f(h(y)) == y`.
+///
+/// This is useful for round trips like serializing a deserialized value.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::right_inverse(
+///         fate,
+///         dice::u32(..),
+///         |s: String| s.parse::<u32>().unwrap(),
+///         |x| x.to_string(),
+///     );
+/// });
+/// ```
+pub fn right_inverse<X, Y>(
+    mut fate: Fate,
+    y_die: impl DieOnce<Y>,
+    f: impl FnOnce(X) -> Y,
+    h: impl FnOnce(Y) -> X,
+) where
+    Y: Debug + Clone + PartialEq,
+{
+    let y = fate.roll(y_die);
+    hint_debug!(y);
+    let x = h(y.clone());
+    let other_y = f(x);
+    hint_debug!(other_y);
+
+    assert_eq!(y, other_y, "right inverse law is violated")
+}
+
+/// Checks that [`PartialEq`] is reflexive, i.e. `x == x`.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::eq_reflexive(fate, dice::vec(dice::u8(..), ..));
+/// });
+/// ```
+pub fn eq_reflexive<T>(mut fate: Fate, die: impl DieOnce<T>)
+where
+    T: Debug + PartialEq,
+{
+    let x = fate.roll(die);
+    hint_debug!(x);
+
+    // The comparison of a value with itself is the purpose of this check
+    #[allow(clippy::eq_op)]
+    let is_reflexive = x == x;
+    assert!(is_reflexive, "reflexivity of PartialEq is violated")
+}
+
+/// Checks that [`PartialEq`] is symmetric, i.e. `x == y` implies `y == x`.
+///
+/// The second value is either a clone of the first or generated independently.
+pub fn eq_symmetric<T>(mut fate: Fate, die: impl Die<T>)
+where
+    T: Debug + Clone + PartialEq,
+{
+    let x = fate.roll(&die);
+    let y = roll_maybe_equal(&mut fate, &die, &x);
+    hint_debug!(x);
+    hint_debug!(y);
+
+    let x_eq_y = x == y;
+    let y_eq_x = y == x;
+    assert_eq!(x_eq_y, y_eq_x, "symmetry of PartialEq is violated")
+}
+
+/// Checks that [`PartialEq`] is transitive, i.e. `x == y` and `y == z` implies `x == z`.
+///
+/// The second and third value are either clones of the previous value or generated
+/// independently.
+pub fn eq_transitive<T>(mut fate: Fate, die: impl Die<T>)
+where
+    T: Debug + Clone + PartialEq,
+{
+    let x = fate.roll(&die);
+    let y = roll_maybe_equal(&mut fate, &die, &x);
+    let z = roll_maybe_equal(&mut fate, &die, &y);
+    hint_debug!(x);
+    hint_debug!(y);
+    hint_debug!(z);
+
+    if x == y && y == z {
+        assert!(x == z, "transitivity of PartialEq is violated")
+    }
+}
+
+/// Checks that [`Ord`] is consistent with [`Eq`] and [`PartialOrd`].
+///
+/// It holds:
+/// - `x.cmp(y) == Ordering::Equal` iff `x == y`
+/// - `x.partial_cmp(y) == Some(x.cmp(y))`
+/// - `x.cmp(y) == y.cmp(x).reverse()`
+///
+/// The second value is either a clone of the first or generated independently.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::ord_consistent_with_eq(fate, dice::string(dice::char(), ..));
+/// });
+/// ```
+pub fn ord_consistent_with_eq<T>(mut fate: Fate, die: impl Die<T>)
+where
+    T: Debug + Clone + Ord,
+{
+    let x = fate.roll(&die);
+    let y = roll_maybe_equal(&mut fate, &die, &x);
+    hint_debug!(x);
+    hint_debug!(y);
+
+    let ordering = x.cmp(&y);
+    hint_debug!(ordering);
+
+    assert_eq!(
+        ordering == Ordering::Equal,
+        x == y,
+        "Ord is not consistent with Eq"
+    );
+    assert_eq!(
+        x.partial_cmp(&y),
+        Some(ordering),
+        "Ord is not consistent with PartialOrd"
+    );
+    assert_eq!(
+        y.cmp(&x),
+        ordering.reverse(),
+        "antisymmetry of Ord is violated"
+    );
+}
+
+/// Checks that [`Ord`] is transitive, i.e. `x <= y` and `y <= z` implies `x <= z`.
+pub fn ord_transitive<T>(mut fate: Fate, die: impl Die<T>)
+where
+    T: Debug + Clone + Ord,
+{
+    let x = fate.roll(&die);
+    let y = roll_maybe_equal(&mut fate, &die, &x);
+    let z = roll_maybe_equal(&mut fate, &die, &y);
+    hint_debug!(x);
+    hint_debug!(y);
+    hint_debug!(z);
+
+    if x <= y && y <= z {
+        assert!(x <= z, "transitivity of Ord is violated")
+    }
+}
+
+/// Checks that [`Hash`] is consistent with [`Eq`], i.e. `x == y` implies that `x` and `y`
+/// have the same hash.
+///
+/// The second value is either a clone of the first or generated independently.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::hash_consistent_with_eq(fate, dice::vec(dice::u8(..), ..));
+/// });
+/// ```
+pub fn hash_consistent_with_eq<T>(mut fate: Fate, die: impl Die<T>)
+where
+    T: Debug + Clone + Eq + Hash,
+{
+    let x = fate.roll(&die);
+    let y = roll_maybe_equal(&mut fate, &die, &x);
+    hint_debug!(x);
+    hint_debug!(y);
+
+    if x == y {
+        assert_eq!(hash(&x), hash(&y), "Hash is not consistent with Eq")
+    }
+}
+
+/// Checks that a clone is equal to the original, i.e. `x.clone() == x`.
+pub fn clone_equal<T>(mut fate: Fate, die: impl DieOnce<T>)
+where
+    T: Debug + Clone + PartialEq,
+{
+    let x = fate.roll(die);
+    let clone = x.clone();
+    hint_debug!(x);
+    hint_debug!(clone);
+
+    assert!(clone == x, "clone is not equal to the original")
+}
+
+/// Checks that the operation is associative, i.e. `op(op(x, y), z) == op(x, op(y, z))`.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::semigroup_associative(fate, dice::string(dice::char(), ..), |x, y| x + &y);
+/// });
+/// ```
+pub fn semigroup_associative<T>(mut fate: Fate, die: impl Die<T>, op: impl Fn(T, T) -> T)
+where
+    T: Debug + Clone + PartialEq,
+{
+    let x = fate.roll(&die);
+    let y = fate.roll(&die);
+    let z = fate.roll(&die);
+    hint_debug!(x);
+    hint_debug!(y);
+    hint_debug!(z);
+
+    let left = op(op(x.clone(), y.clone()), z.clone());
+    let right = op(x, op(y, z));
+    hint_debug!(left);
+    hint_debug!(right);
+
+    assert_eq!(left, right, "associativity is violated")
+}
+
+/// Checks that `identity` is the identity element of the operation, i.e.
+/// `op(identity, x) == x` and `op(x, identity) == x`.
+///
+/// Together with [`semigroup_associative`] this checks the monoid laws.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::monoid_identity(fate, dice::u64(..), 0, |x, y| x.wrapping_add(y));
+/// });
+/// ```
+pub fn monoid_identity<T>(mut fate: Fate, die: impl DieOnce<T>, identity: T, op: impl Fn(T, T) -> T)
+where
+    T: Debug + Clone + PartialEq,
+{
+    let x = fate.roll(die);
+    hint_debug!(x);
+    hint_debug!(identity);
+
+    let left = op(identity.clone(), x.clone());
+    hint_debug!(left);
+    assert_eq!(left, x, "left identity is violated");
+
+    let right = op(x.clone(), identity);
+    hint_debug!(right);
+    assert_eq!(right, x, "right identity is violated");
+}
+
+/// Checks that [`Iterator::size_hint`] is consistent with the actual number of remaining
+/// elements.
+///
+/// The check consumes the iterator, hence the iterator must be finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     let iter_die = dice::vec(dice::u8(..), ..).map(|vec| vec.into_iter());
+///     laws::iterator_size_hint(fate, iter_die);
+/// });
+/// ```
+pub fn iterator_size_hint<I>(mut fate: Fate, iter_die: impl DieOnce<I>)
+where
+    I: Iterator,
+{
+    let mut iter = fate.roll(iter_die);
+
+    let mut size_hints = vec![iter.size_hint()];
+    while iter.next().is_some() {
+        size_hints.push(iter.size_hint());
+    }
+    let len = size_hints.len() - 1;
+    hint_debug!(len);
+
+    for (index, (lower, upper)) in size_hints.into_iter().enumerate() {
+        let remaining = len - index;
+        let lower_is_valid = lower <= remaining;
+        let upper_is_valid = upper.is_none_or(|upper| remaining <= upper);
+        if !lower_is_valid || !upper_is_valid {
+            hint!(
+                "after {} elements the size hint is {:?}, but {} elements remain",
+                index,
+                (lower, upper),
+                remaining,
+            );
+            panic!("size hint of Iterator is violated");
+        }
+    }
+}
+
+/// Checks that [`ExactSizeIterator::len`] and [`Iterator::size_hint`] return the exact
+/// number of remaining elements.
+///
+/// The check consumes the iterator.
+pub fn exact_size_iterator_len<I>(mut fate: Fate, iter_die: impl DieOnce<I>)
+where
+    I: ExactSizeIterator,
+{
+    let mut iter = fate.roll(iter_die);
+
+    let mut lens = vec![(iter.len(), iter.size_hint())];
+    while iter.next().is_some() {
+        lens.push((iter.len(), iter.size_hint()));
+    }
+    let actual_len = lens.len() - 1;
+    hint_debug!(actual_len);
+
+    for (index, (len, size_hint)) in lens.into_iter().enumerate() {
+        let remaining = actual_len - index;
+        if len != remaining || size_hint != (remaining, Some(remaining)) {
+            hint!(
+                "after {} elements the len is {} and the size hint is {:?}, but {} elements remain",
+                index,
+                len,
+                size_hint,
+                remaining,
+            );
+            panic!("len of ExactSizeIterator is violated");
+        }
+    }
+}
+
+/// Checks that [`DoubleEndedIterator`] is consistent with [`Iterator`].
+///
+/// The elements are consumed randomly from the front and the back. They must be equal to the
+/// elements of the corresponding positions of the forward iteration.
+///
+/// The check consumes the iterator, hence the iterator must be finite.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     let iter_die = dice::vec(dice::u8(..), ..).map(|vec| vec.into_iter());
+///     laws::double_ended_iterator(fate, iter_die);
+/// });
+/// ```
+pub fn double_ended_iterator<I>(mut fate: Fate, iter_die: impl DieOnce<I>)
+where
+    I: DoubleEndedIterator + Clone,
+    I::Item: Debug + PartialEq,
+{
+    let mut iter = fate.roll(iter_die);
+    let forward = iter.clone().collect::<Vec<_>>();
+    hint_debug!(forward);
+
+    let (mut front, mut back) = (0, forward.len());
+    loop {
+        let from_back = fate.roll(dice::bool());
+        let (next, expected) = if from_back {
+            let next = iter.next_back();
+            let expected = back.checked_sub(1).filter(|&index| index >= front);
+            if expected.is_some() {
+                back -= 1;
+            }
+            (next, expected)
+        } else {
+            let next = iter.next();
+            let expected = Some(front).filter(|&index| index < back);
+            if expected.is_some() {
+                front += 1;
+            }
+            (next, expected)
+        };
+
+        let expected = expected.map(|index| &forward[index]);
+        if next.as_ref() != expected {
+            hint!(
+                "{} returned {:?}, but expected {:?}",
+                if from_back { "next_back" } else { "next" },
+                next,
+                expected,
+            );
+            panic!("DoubleEndedIterator is not consistent with Iterator");
+        }
+
+        if next.is_none() {
+            break;
+        }
+    }
+}
+
+// Converts the payload of a panic into a message if possible.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(string) = payload.downcast_ref::<String>() {
+        string.clone()
+    } else if let Some(&str) = payload.downcast_ref::<&str>() {
+        str.to_string()
+    } else {
+        "The panic has an unknown type and cannot be displayed.".to_string()
+    }
+}
+
+// Runs the implementation and catches its panic.
+fn run_catching<I, O>(implementation: impl FnOnce(I) -> O, input: I) -> Result<O, String> {
+    catch_unwind(AssertUnwindSafe(|| implementation(input)))
+        .map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Checks that both implementations return equal outputs for the same input.
+///
+/// This is a shortcut for [`differential_by`] with [`PartialEq`] as comparator.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::differential(
+///         fate,
+///         dice::vec(dice::u8(..), ..),
+///         |vec| vec.iter().map(|&x| u64::from(x)).sum::<u64>(),
+///         |vec| vec.iter().fold(0, |acc, &x| acc + u64::from(x)),
+///     );
+/// });
+/// ```
+pub fn differential<I, O>(
+    fate: Fate,
+    input_die: impl DieOnce<I>,
+    reference: impl FnOnce(I) -> O,
+    candidate: impl FnOnce(I) -> O,
+) where
+    I: Debug + Clone,
+    O: Debug + PartialEq,
+{
+    differential_by(fate, input_die, reference, candidate, |left, right| {
+        left == right
+    })
+}
+
+/// Checks that both implementations agree on the same input.
+///
+/// The input is passed to the reference implementation and to the candidate implementation.
+/// Panics of either implementation are caught. The implementations agree if both panic or if
+/// both return outputs that are equal with respect to the given comparator. Otherwise the
+/// input and both outcomes are added as hints and the check panics.
+///
+/// The stat `differential outcome` counts how often each implementation panicked.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use dicetest::prelude::*;
+/// use dicetest::laws;
+///
+/// Dicetest::repeatedly().run(|fate| {
+///     laws::differential_by(
+///         fate,
+///         dice::vec(dice::u8(..), ..),
+///         |vec| vec.into_iter().collect::<HashSet<_>>(),
+///         |mut vec| {
+///             vec.sort();
+///             vec.dedup();
+///             vec
+///         },
+///         |set, vec| set.len() == vec.len() && vec.iter().all(|x| set.contains(x)),
+///     );
+/// });
+/// ```
+pub fn differential_by<I, R, C>(
+    mut fate: Fate,
+    input_die: impl DieOnce<I>,
+    reference: impl FnOnce(I) -> R,
+    candidate: impl FnOnce(I) -> C,
+    compare: impl FnOnce(&R, &C) -> bool,
+) where
+    I: Debug + Clone,
+    R: Debug,
+    C: Debug,
+{
+    let input = fate.roll(input_die);
+
+    let reference_result = run_catching(reference, input.clone());
+    let candidate_result = run_catching(candidate, input.clone());
+
+    stat!(
+        "differential outcome",
+        "{}",
+        match (&reference_result, &candidate_result) {
+            (Ok(_), Ok(_)) => "both returned",
+            (Err(_), Ok(_)) => "reference panicked",
+            (Ok(_), Err(_)) => "candidate panicked",
+            (Err(_), Err(_)) => "both panicked",
+        },
+    );
+
+    let agree = match (&reference_result, &candidate_result) {
+        (Ok(reference_output), Ok(candidate_output)) => compare(reference_output, candidate_output),
+        (Err(_), Err(_)) => true,
+        _ => false,
+    };
+
+    if !agree {
+        hint_debug!(input);
+        match &reference_result {
+            Ok(output) => {
+                hint!("reference returned {:?}", output);
+            }
+            Err(message) => {
+                hint!("reference panicked: {}", message);
+            }
+        }
+        match &candidate_result {
+            Ok(output) => {
+                hint!("candidate returned {:?}", output);
+            }
+            Err(message) => {
+                hint!("candidate panicked: {}", message);
+            }
+        }
+        panic!("reference and candidate implementation disagree");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{Hash, Hasher};
+
+    use crate::prelude::*;
+    use crate::{Limit, Prng, laws};
+
+    #[test]
+    fn laws_hold_for_std_types() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let vec_die = dice::vec(dice::u8(..), ..);
+            laws::eq_reflexive(fate.copy(), &vec_die);
+            laws::eq_symmetric(fate.copy(), &vec_die);
+            laws::eq_transitive(fate.copy(), &vec_die);
+            laws::ord_consistent_with_eq(fate.copy(), &vec_die);
+            laws::ord_transitive(fate.copy(), &vec_die);
+            laws::hash_consistent_with_eq(fate.copy(), &vec_die);
+            laws::clone_equal(fate.copy(), &vec_die);
+            laws::semigroup_associative(fate.copy(), &vec_die, |mut x, y| {
+                x.extend(y);
+                x
+            });
+            laws::monoid_identity(fate.copy(), &vec_die, Vec::new(), |mut x, y| {
+                x.extend(y);
+                x
+            });
+
+            let iter_die = dice::vec(dice::u8(..), ..).map(|vec| vec.into_iter());
+            laws::iterator_size_hint(fate.copy(), &iter_die);
+            laws::exact_size_iterator_len(fate.copy(), &iter_die);
+            laws::double_ended_iterator(fate.copy(), &iter_die);
+        })
+    }
+
+    // A type whose `Hash` implementation is inconsistent with its `Eq` implementation.
+    #[derive(Debug, Clone, Eq)]
+    struct CaseInsensitive(String);
+
+    impl PartialEq for CaseInsensitive {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.eq_ignore_ascii_case(&other.0)
+        }
+    }
+
+    impl Hash for CaseInsensitive {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state)
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn hash_consistent_with_eq_detects_violation() {
+        Dicetest::repeatedly().run(|fate| {
+            let die = dice::one_of()
+                .two("a", "A")
+                .map(|s| CaseInsensitive(s.to_string()));
+            laws::hash_consistent_with_eq(fate, die);
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn semigroup_associative_detects_violation() {
+        Dicetest::repeatedly().run(|fate| {
+            laws::semigroup_associative(fate, dice::i32(..), |x, y| x.wrapping_sub(y));
+        })
+    }
+
+    // An iterator with a wrong size hint.
+    #[derive(Clone)]
+    struct Lying(std::ops::Range<u8>);
+
+    impl Iterator for Lying {
+        type Item = u8;
+
+        fn next(&mut self) -> Option<u8> {
+            self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (0, Some(0))
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn iterator_size_hint_detects_violation() {
+        Dicetest::repeatedly().run(|fate| {
+            laws::iterator_size_hint(fate, dice::just(Lying(0..3)));
+        })
+    }
+
+    #[test]
+    fn differential_accepts_equal_implementations() {
+        Dicetest::repeatedly().run(|fate| {
+            laws::differential(
+                fate,
+                dice::u8(..),
+                |x| x.checked_mul(2).expect("overflow"),
+                |x| x.checked_add(x).expect("overflow"),
+            );
+        })
+    }
+
+    #[test]
+    fn differential_detects_different_outputs() {
+        let (result, hints) = hints::collect(|| {
+            std::panic::catch_unwind(|| {
+                let mut prng = Prng::from_seed(0x5EED.into());
+                let fate = Fate::new(&mut prng, Limit::default());
+                laws::differential(fate, dice::just(3), |x: u8| x, |x: u8| x + 1);
+            })
+        });
+
+        assert!(result.is_err());
+        if cfg!(feature = "hints") {
+            let texts = hints
+                .0
+                .into_iter()
+                .map(|hint| hint.text)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                texts,
+                vec!["input = 3", "reference returned 3", "candidate returned 4"]
+            );
+        }
+    }
+
+    #[test]
+    fn differential_detects_one_sided_panic() {
+        let (result, hints) = hints::collect(|| {
+            std::panic::catch_unwind(|| {
+                let mut prng = Prng::from_seed(0x5EED.into());
+                let fate = Fate::new(&mut prng, Limit::default());
+                laws::differential(
+                    fate,
+                    dice::just(0),
+                    |x: u8| x,
+                    |_: u8| -> u8 { panic!("boom") },
+                );
+            })
+        });
+
+        assert!(result.is_err());
+        if cfg!(feature = "hints") {
+            let texts = hints
+                .0
+                .into_iter()
+                .map(|hint| hint.text)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                texts,
+                vec![
+                    "input = 0",
+                    "reference returned 0",
+                    "candidate panicked: boom"
+                ]
+            );
+        }
+    }
+}
@@ -60,6 +60,8 @@ mod util;
 mod seed;
 pub use seed::Seed;
 
+pub mod engine;
+
 mod prng;
 pub use prng::Prng;
 
@@ -81,6 +83,9 @@ pub use die::Die;
 mod dice_trait;
 pub use dice_trait::{Dice, die};
 
+mod codice_trait;
+pub use codice_trait::{Codice, codie};
+
 #[cfg(feature = "derive")]
 mod dice_derive;
 #[cfg(feature = "derive")]
@@ -92,20 +97,26 @@ pub mod codice;
 
 pub mod dice;
 
+pub mod laws;
+
+pub mod statistics;
+
 pub mod hints;
 
 pub mod stats;
 
 pub mod runner;
 
+pub mod bench;
+
+pub mod die_check;
+pub use die_check::check_die;
+
 mod frontend;
 pub use frontend::Dicetest;
 
 pub mod prelude;
 
-#[cfg(test)]
-mod asserts;
-
 // Test examples from the readme.
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
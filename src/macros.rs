@@ -111,6 +111,96 @@ macro_rules! stat_debug {
     };
 }
 
+/// Defines tests with typed parameters that are generated randomly.
+///
+/// This declarative macro is an interim substitute for an attribute macro `#[dicetest]` that
+/// would need the feature `derive`.
+///
+/// Each function is converted into a `#[test]` function that runs its body with
+/// [`Dicetest::repeatedly`]. The parameters are generated with their [`Dice`] implementation
+/// or with the [`Die`] given after `=`. The parameter values are added as hints.
+///
+/// The optional attribute `#[dicetest(...)]` must be the first attribute. Each of its options
+/// `name = value` calls the method `name` of [`Dicetest`] with `value`. All other attributes
+/// are kept.
+///
+/// Because of macro hygiene the body can't access the [`Fate`] that generates the parameters.
+/// Values that depend on other values must be generated by a single parameter, e.g. with
+/// [`dice::from_fn`].
+///
+/// [`Fate`]: crate::Fate
+/// [`dice::from_fn`]: crate::dice::from_fn
+/// [`Dicetest`]: crate::Dicetest
+/// [`Dicetest::repeatedly`]: crate::Dicetest::repeatedly
+/// [`Dice`]: crate::Dice
+/// [`Die`]: crate::Die
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+///
+/// dicetest! {
+///     fn reverse_is_involution(vec: Vec<u8>) {
+///         let mut other_vec = vec.clone();
+///         other_vec.reverse();
+///         other_vec.reverse();
+///         assert_eq!(vec, other_vec);
+///     }
+///
+///     #[dicetest(passes = 500, stats_enabled = true)]
+///     #[ignore]
+///     fn division_is_inverse(x: u8, y: u8 = dice::u8(1..)) {
+///         stat_debug!({ x % y == 0 });
+///         assert_eq!(x / y * y + x % y, x);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! dicetest {
+    () => {};
+    (@die $type:ty) => {
+        $crate::die::<$type>()
+    };
+    (@die $type:ty, $die:expr) => {
+        $die
+    };
+    (
+        #[dicetest $(($($option:ident = $value:expr),* $(,)?))?]
+        $(#[$meta:meta])*
+        fn $name:ident($($param:ident: $type:ty $(= $die:expr)?),* $(,)?) $body:block
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        #[test]
+        fn $name() {
+            $crate::Dicetest::repeatedly()
+                $($(.$option($value))*)?
+                .run(|#[allow(unused_mut, unused_variables)] mut fate| {
+                    $(
+                        let $param: $type = fate.roll($crate::dicetest!(@die $type $(, $die)?));
+                        $crate::hint_debug!($param);
+                    )*
+                    $body
+                })
+        }
+
+        $crate::dicetest! { $($rest)* }
+    };
+    (
+        $(#[$meta:meta])*
+        fn $name:ident $params:tt $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::dicetest! {
+            #[dicetest]
+            $(#[$meta])*
+            fn $name $params $body
+            $($rest)*
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -193,4 +283,24 @@ mod tests {
             stat_debug!((if true { 1 } else { 2 }));
         }
     }
+
+    dicetest! {
+        fn macro_dicetest_rolls_parameters(
+            x: u8 = crate::dice::u8(..=2),
+            y: u8 = crate::dice::u8(1..=3),
+        ) {
+            assert!(x <= 2);
+            assert!((1..=3).contains(&y));
+        }
+
+        #[dicetest(passes = 10, stats_enabled = false,)]
+        fn macro_dicetest_accepts_options(
+            vec: Vec<u8> = crate::dice::vec(crate::dice::u8(..), 2..=4),
+        ) {
+            assert!((2..=4).contains(&vec.len()));
+        }
+
+        #[dicetest]
+        fn macro_dicetest_accepts_no_parameters() {}
+    }
 }
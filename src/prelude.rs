@@ -1,6 +1,6 @@
 //! Contains the most useful imports for writing tests and value generators.
 
 pub use crate::{
-    Codie, Dice, Dicetest, Die, DieOnce, Fate, codice, dice, die, hint, hint_debug, hints, stat,
-    stat_debug, stats,
+    Codice, Codie, Dice, Dicetest, Die, DieOnce, Fate, codice, codie, dice, dicetest, die, hint,
+    hint_debug, hints, stat, stat_debug, stats,
 };
@@ -1,37 +1,68 @@
 use std::hash::BuildHasher;
 #[allow(deprecated)]
 use std::hash::SipHasher;
-use std::num::Wrapping;
 
 use crate::Seed;
-use crate::util::conversion;
+use crate::engine::{Engine, EngineKind, Jsf64, SplitMix64, Xoshiro256StarStar};
 
 /// A pseudorandom number generator.
 ///
-/// The algorithms are based on [this article] by Bob Jenkins.
+/// It uses one of the engines of the module [`engine`]. By default it uses [`Jsf64`], which is
+/// based on [this article] by Bob Jenkins.
 ///
+/// [`engine`]: crate::engine
 /// [this article]: http://burtleburtle.net/bob/rand/smallprng.html
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Prng {
-    state: (u64, u64, u64, u64),
+    engine: Engines,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Engines {
+    Jsf64(Jsf64),
+    Xoshiro256StarStar(Xoshiro256StarStar),
+    SplitMix64(SplitMix64),
+}
+
+// Calls the given expression with the engine of the `Prng`.
+macro_rules! with_engine {
+    ($prng:expr, $engine:ident => $body:expr) => {
+        match $prng {
+            Engines::Jsf64($engine) => $body,
+            Engines::Xoshiro256StarStar($engine) => $body,
+            Engines::SplitMix64($engine) => $body,
+        }
+    };
 }
 
 impl Prng {
-    /// Creates a [`Prng`] whose internal state is initialized with the given seed.
+    /// Creates a [`Prng`] with the default engine whose internal state is initialized with the
+    /// given seed.
     ///
     /// The result has a satisfying cycle length.
     pub fn from_seed(seed: Seed) -> Prng {
-        let state = (0xf1ea_5eed, seed.0, seed.0, seed.0);
-        let mut prng = Prng { state };
-        for _ in 0..20 {
-            prng.next_number();
+        Self::from_engine_seed(EngineKind::default(), seed)
+    }
+
+    /// Creates a [`Prng`] with the given engine whose internal state is initialized with the
+    /// given seed.
+    pub fn from_engine_seed(kind: EngineKind, seed: Seed) -> Prng {
+        match kind {
+            EngineKind::Jsf64 => Jsf64::from_seed(seed).into(),
+            EngineKind::Xoshiro256StarStar => Xoshiro256StarStar::from_seed(seed).into(),
+            EngineKind::SplitMix64 => SplitMix64::from_seed(seed).into(),
         }
-        prng
     }
 
-    /// Creates a [`Prng`] using the given byte array as internal state.
+    /// Returns the kind of the engine used by `self`.
+    pub fn engine_kind(&self) -> EngineKind {
+        with_engine!(&self.engine, engine => engine_kind(engine))
+    }
+
+    /// Creates a [`Prng`] with the given engine using the given bytes as internal state.
     ///
-    /// This function is a left and right inverse for [`to_bytes`].
+    /// This function is a left inverse for [`to_bytes`]. Returns `None` if the bytes are not a
+    /// valid internal state of the engine.
     ///
     /// A satisfying cycle length is only guaranteed for bytes from [`to_bytes`] called
     /// with an [`Prng`] that has a satisfying cycle length. Other bytes should not be passed to this
@@ -39,88 +70,80 @@ impl Prng {
     ///
     /// [`to_bytes`]: Prng::to_bytes
     /// [`from_seed`]: Prng::from_seed
-    pub fn from_bytes(state_bytes: [u8; 32]) -> Prng {
-        #[rustfmt::skip]
-        let [
-            a0, a1, a2, a3, a4, a5, a6, a7,
-            b0, b1, b2, b3, b4, b5, b6, b7,
-            c0, c1, c2, c3, c4, c5, c6, c7,
-            d0, d1, d2, d3, d4, d5, d6, d7,
-        ] = state_bytes;
-
-        let a = conversion::bytes_to_u64([a0, a1, a2, a3, a4, a5, a6, a7]);
-        let b = conversion::bytes_to_u64([b0, b1, b2, b3, b4, b5, b6, b7]);
-        let c = conversion::bytes_to_u64([c0, c1, c2, c3, c4, c5, c6, c7]);
-        let d = conversion::bytes_to_u64([d0, d1, d2, d3, d4, d5, d6, d7]);
-
-        let state = (a, b, c, d);
-        Prng { state }
+    pub fn from_bytes(kind: EngineKind, state_bytes: &[u8]) -> Option<Prng> {
+        match kind {
+            EngineKind::Jsf64 => Jsf64::from_bytes(state_bytes).map(Prng::from),
+            EngineKind::Xoshiro256StarStar => {
+                Xoshiro256StarStar::from_bytes(state_bytes).map(Prng::from)
+            }
+            EngineKind::SplitMix64 => SplitMix64::from_bytes(state_bytes).map(Prng::from),
+        }
     }
 
-    /// Returns the internal state as a byte array.
+    /// Returns the internal state of the engine as bytes.
     ///
-    /// This function is a left and right inverse for `from_bytes`.
+    /// This function is a left inverse for [`from_bytes`] if the latter is called with the
+    /// kind of the engine.
     ///
     /// [`from_bytes`]: Prng::from_bytes
-    pub fn to_bytes(&self) -> [u8; 32] {
-        let (a, b, c, d) = self.state;
-
-        let [a0, a1, a2, a3, a4, a5, a6, a7] = conversion::u64_to_bytes(a);
-        let [b0, b1, b2, b3, b4, b5, b6, b7] = conversion::u64_to_bytes(b);
-        let [c0, c1, c2, c3, c4, c5, c6, c7] = conversion::u64_to_bytes(c);
-        let [d0, d1, d2, d3, d4, d5, d6, d7] = conversion::u64_to_bytes(d);
-
-        #[rustfmt::skip]
-        let state_bytes = [
-            a0, a1, a2, a3, a4, a5, a6, a7,
-            b0, b1, b2, b3, b4, b5, b6, b7,
-            c0, c1, c2, c3, c4, c5, c6, c7,
-            d0, d1, d2, d3, d4, d5, d6, d7,
-        ];
-
-        state_bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        with_engine!(&self.engine, engine => engine.to_bytes())
     }
 
-    #[allow(clippy::many_single_char_names)]
     /// Returns the next pseudorandom number.
     pub fn next_number(&mut self) -> u64 {
-        let (a, b, c, d) = self.state;
-
-        // We use `Wrapping` because overflow and underflow is intended
-        let Wrapping(e) = Wrapping(a) - Wrapping(b.rotate_left(7));
-        let Wrapping(f) = Wrapping(b) ^ Wrapping(c.rotate_left(13));
-        let Wrapping(g) = Wrapping(c) + Wrapping(d.rotate_left(37));
-        let Wrapping(h) = Wrapping(d) + Wrapping(e);
-        let Wrapping(i) = Wrapping(e) + Wrapping(a);
+        with_engine!(&mut self.engine, engine => engine.next_number())
+    }
 
-        self.state = (f, g, h, i);
-        i
+    /// Fills the given buffer with pseudorandom bytes.
+    ///
+    /// Each pseudorandom number fills 8 bytes, the bytes of the last number that don't fit into
+    /// the buffer are discarded.
+    pub fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(8) {
+            let number_bytes = self.next_number().to_le_bytes();
+            chunk.copy_from_slice(&number_bytes[..chunk.len()]);
+        }
     }
 
-    #[allow(clippy::many_single_char_names)]
     /// Reinitialize the internal state of `self` using the current internal state and the given
     /// seed.
     pub fn reseed(&mut self, seed: Seed) {
-        let (a, b, c, d) = self.state;
+        with_engine!(&mut self.engine, engine => engine.reseed(seed))
+    }
 
-        // The implementation is inspired by ScalaCheck.
-        let n0 = (seed.0 >> 32) & 0xffff_ffff;
-        let n1 = seed.0 & 0xffff_ffff;
+    /// Splits off a new [`Prng`] from `self`. The internal state of the new [`Prng`] is generated
+    /// with `self`.
+    pub fn fork(&mut self) -> Prng {
+        with_engine!(&mut self.engine, engine => engine.fork().into())
+    }
+}
 
-        self.state = (a ^ n0, b ^ n1, c, d);
+fn engine_kind<E: Engine>(_: &E) -> EngineKind {
+    E::KIND
+}
 
-        for _ in 0..16 {
-            self.next_number();
+impl From<Jsf64> for Prng {
+    fn from(engine: Jsf64) -> Self {
+        Prng {
+            engine: Engines::Jsf64(engine),
         }
     }
+}
 
-    /// Splits off a new [`Prng`] from `self`. The internal state of the new [`Prng`] is generated
-    /// with `self`.
-    pub fn fork(&mut self) -> Prng {
-        let random_number = self.next_number();
-        let mut reseeded_prng = self.clone();
-        reseeded_prng.reseed(random_number.into());
-        reseeded_prng
+impl From<Xoshiro256StarStar> for Prng {
+    fn from(engine: Xoshiro256StarStar) -> Self {
+        Prng {
+            engine: Engines::Xoshiro256StarStar(engine),
+        }
+    }
+}
+
+impl From<SplitMix64> for Prng {
+    fn from(engine: SplitMix64) -> Self {
+        Prng {
+            engine: Engines::SplitMix64(engine),
+        }
     }
 }
 
@@ -138,8 +161,10 @@ impl BuildHasher for Prng {
 
 #[cfg(test)]
 mod tests {
+    use crate::Prng;
+    use crate::engine::EngineKind;
     use crate::prelude::*;
-    use crate::{Prng, asserts};
+    use crate::statistics;
 
     #[test]
     fn from_seed_must_not_have_cycle_length_zero() {
@@ -159,31 +184,95 @@ mod tests {
         })
     }
 
+    fn prng_die() -> impl Die<Prng> {
+        dice::zip()
+            .two(dice::one_of_slice(&EngineKind::ALL), dice::u64(..))
+            .map(|(kind, seed)| Prng::from_engine_seed(kind, seed.into()))
+    }
+
     #[test]
     fn from_bytes_is_left_inverse() {
-        Dicetest::repeatedly().run(|fate| {
-            asserts::left_inverse(
-                fate,
-                dice::from_fn(|mut fate| fate.fork_prng()),
-                |prng| prng.to_bytes(),
-                Prng::from_bytes,
-            );
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.roll(prng_die());
+            let bytes = prng.to_bytes();
+
+            hint_debug!(prng);
+            hint_debug!(bytes);
+
+            assert_eq!(Prng::from_bytes(prng.engine_kind(), &bytes), Some(prng));
         })
     }
 
     #[test]
     fn to_bytes_is_left_inverse() {
-        Dicetest::repeatedly().run(|fate| {
-            asserts::left_inverse(fate, dice::array(dice::u8(..)), Prng::from_bytes, |prng| {
-                prng.to_bytes()
+        Dicetest::repeatedly().run(|mut fate| {
+            let bytes: [u8; 32] = fate.roll(dice::array(dice::u8(..)));
+            let prng = Prng::from_bytes(EngineKind::Jsf64, &bytes).unwrap();
+
+            hint_debug!(bytes);
+            hint_debug!(prng);
+
+            assert_eq!(prng.to_bytes(), bytes);
+        })
+    }
+
+    #[test]
+    fn fill_bytes_uses_whole_numbers() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let prng = fate.roll(prng_die());
+            let len = fate.roll(dice::length(..));
+
+            let mut bytes = vec![0; len];
+            prng.clone().fill_bytes(&mut bytes);
+
+            let mut other_prng = prng.clone();
+            let expected_bytes = (0..len.div_ceil(8))
+                .flat_map(|_| other_prng.next_number().to_le_bytes())
+                .take(len)
+                .collect::<Vec<_>>();
+
+            assert_eq!(bytes, expected_bytes);
+        })
+    }
+
+    #[test]
+    fn from_seed_uses_default_engine() {
+        let prng = Prng::from_seed(42.into());
+        assert_eq!(prng.engine_kind(), EngineKind::Jsf64);
+    }
+
+    #[test]
+    fn fork_keeps_engine() {
+        Dicetest::repeatedly().run(|mut fate| {
+            let mut prng = fate.roll(prng_die());
+            let fork = prng.fork();
+            assert_eq!(fork.engine_kind(), prng.engine_kind());
+        })
+    }
+
+    #[test]
+    fn fork_is_independent_of_parent() {
+        Dicetest::repeatedly().passes(10).run(|fate| {
+            // If the fork is independent, the XOR of both next numbers is uniformly distributed
+            let die = dice::from_fn(|mut fate| {
+                let kind = fate.roll(dice::one_of_slice(&EngineKind::ALL));
+                let seed = fate.roll(dice::uni_u64(..));
+                let mut prng = Prng::from_engine_seed(kind, seed.into());
+                let mut fork = prng.fork();
+                prng.next_number() ^ fork.next_number()
             });
+            let expected = [1.0 / 16.0; 16];
+            let p_value =
+                statistics::chi_squared(fate, die, 10_000, &expected, |&x| (x >> 60) as usize);
+            hint_debug!(p_value);
+            assert!(p_value > 1e-6);
         })
     }
 
     #[test]
     fn reseed_changes_prng() {
         Dicetest::repeatedly().run(|mut fate| {
-            let prng = fate.fork_prng();
+            let prng = fate.roll(prng_die());
             let seed = fate.roll(dice::u64(..)).into();
 
             let mut prng_reseeded = prng.clone();
@@ -3,14 +3,15 @@
 //! The runner mainly exists for implementing [`Dicetest`]. You probably want to use [`Dicetest`]
 //! instead of using the runner directly.
 //!
-//! The modules [`once`] and [`repeatedly`] contains runner functions with different strategies.
+//! The modules [`once`], [`repeatedly`] and [`sample`] contains runner functions with different
+//! strategies.
 //!
 //! [`Dicetest`]: crate::Dicetest
 
 mod util;
 
 mod limit_series;
-use limit_series::LimitSeries;
+pub(crate) use limit_series::LimitSeries;
 
 mod error;
 pub use error::Error;
@@ -18,3 +19,5 @@ pub use error::Error;
 pub mod once;
 
 pub mod repeatedly;
+
+pub mod sample;
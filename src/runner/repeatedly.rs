@@ -71,6 +71,12 @@ pub struct Counterexample {
     /// The limit for dynamic data structures the counterexample has used for generating
     /// test data.
     pub limit: Limit,
+    /// The index of the random test run that has failed or `None` if a regression test has
+    /// failed.
+    ///
+    /// Together with the initial state of the number generator passed to [`run`] and the
+    /// [`Config`] it identifies the counterexample, see [`fast_forward`].
+    pub pass: Option<u64>,
     /// The hints collected during the counterexample run.
     ///
     /// If hints are enabled, the runner tries to rerun the counterexample to collect hints.
@@ -96,7 +102,9 @@ pub struct Report {
 /// Runs the test repeatedly with the given configuration and different seeds.
 ///
 /// The test will be run until the configured number of passes has been reached or a test run
-/// has failed.
+/// has failed. Each random test run uses its own [`Prng`] that is derived from the given one and
+/// the index of the pass, hence the parameters of a random test run can be reconstructed via
+/// [`fast_forward`].
 pub fn run<T>(prng: Prng, config: &Config, test: T) -> Report
 where
     T: Fn(Fate) + UnwindSafe + RefUnwindSafe,
@@ -122,9 +130,31 @@ where
     }
 }
 
+/// Returns the parameters the random test run with the given index would use if [`run`] would
+/// be called with the same [`Prng`] and [`Config`].
+///
+/// Returns `None` if the index is not less than [`Config::passes`]. The parameters are derived
+/// directly from the index, hence the time doesn't depend on the index.
+pub fn fast_forward(prng: Prng, config: &Config, pass: u64) -> Option<Regression> {
+    let limit_series = LimitSeries::new(config.start_limit, config.end_limit, config.passes);
+    let limit = limit_series.nth(pass)?;
+
+    Some(Regression {
+        prng: pass_prng(&prng, pass),
+        limit,
+    })
+}
+
+// Derives the `Prng` for the random test run with the given index.
+pub(crate) fn pass_prng(prng: &Prng, pass: u64) -> Prng {
+    let mut pass_prng = prng.clone();
+    pass_prng.reseed(pass.into());
+    pass_prng
+}
+
 fn search_counterexample<T>(
     regressions: &[Regression],
-    mut prng: Prng,
+    prng: Prng,
     limit_series: LimitSeries,
     test: &T,
 ) -> (u64, Option<Counterexample>)
@@ -144,6 +174,7 @@ where
             let counterexample = Counterexample {
                 prng: regression.prng.clone(),
                 limit: regression.limit,
+                pass: None,
                 hints: None,
                 error: Error(err),
             };
@@ -153,37 +184,30 @@ where
         passes += 1;
     }
 
-    let mut limits = limit_series.into_iter();
-
-    loop {
-        let limit = match limits.next() {
-            None => return (passes, None),
-            Some(limit) => limit,
-        };
-
-        let prng_before_run = prng.clone();
+    for (pass, limit) in (0..).zip(limit_series.into_iter()) {
+        let pass_prng = pass_prng(&prng, pass);
 
         let test_result = catch_unwind(|| {
+            let mut prng = pass_prng.clone();
             let fate = Fate::new(&mut prng, limit);
             test(fate);
-            prng
         });
 
-        prng = match test_result {
-            Err(err) => {
-                let counterexample = Counterexample {
-                    prng: prng_before_run,
-                    limit,
-                    hints: None,
-                    error: Error(err),
-                };
-                return (passes, Some(counterexample));
-            }
-            Ok(prng_after_run) => prng_after_run,
-        };
+        if let Err(err) = test_result {
+            let counterexample = Counterexample {
+                prng: pass_prng,
+                limit,
+                pass: Some(pass),
+                hints: None,
+                error: Error(err),
+            };
+            return (passes, Some(counterexample));
+        }
 
         passes += 1;
     }
+
+    (passes, None)
 }
 
 fn rerun_counterexample<T>(counterexample: Counterexample, test: &T) -> Counterexample
@@ -214,9 +238,13 @@ where
 #[cfg(test)]
 mod tests {
     use core::panic;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
     use std::sync::atomic::{AtomicU64, Ordering};
 
-    use crate::runner::repeatedly::{Config, run};
+    use crate::engine::EngineKind;
+
+    use crate::runner::repeatedly::{Config, fast_forward, run};
     use crate::{Prng, Seed, hints};
 
     use super::Regression;
@@ -382,6 +410,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fast_forward_reproduces_counterexample() {
+        let counter = AtomicU64::new(1);
+        let config = default_config();
+        let report = run(default_prng(), &config, |mut fate| {
+            let run = counter.fetch_add(1, Ordering::Relaxed);
+            let number = fate.next_number();
+            if run == 10 {
+                panic!("{}", number)
+            }
+        });
+        let counterexample = report.counterexample.unwrap();
+        assert_eq!(counterexample.pass, Some(9));
+
+        let regression = fast_forward(default_prng(), &config, 9).unwrap();
+        assert_eq!(regression.prng, counterexample.prng);
+        assert_eq!(regression.limit, counterexample.limit);
+    }
+
+    #[test]
+    fn passes_use_unrelated_prngs() {
+        for kind in EngineKind::ALL {
+            let numbers = Mutex::new(Vec::new());
+            let config = Config {
+                passes: 1000,
+                ..default_config()
+            };
+            run(
+                Prng::from_engine_seed(kind, Seed(42)),
+                &config,
+                |mut fate| {
+                    let number = fate.next_number();
+                    numbers.lock().unwrap().push(number);
+                },
+            );
+
+            let numbers = numbers.into_inner().unwrap();
+            let distinct_count = numbers.iter().collect::<HashSet<_>>().len();
+            assert_eq!(distinct_count, 1000);
+
+            // Each bit is set in about half of the numbers
+            for bit in 0..64 {
+                let ones = numbers.iter().filter(|n| *n >> bit & 1 == 1).count();
+                assert!((400..=600).contains(&ones), "{kind} {bit} {ones}");
+            }
+        }
+    }
+
+    #[test]
+    fn fast_forward_fails_if_pass_out_of_bounds() {
+        let config = default_config();
+        assert!(fast_forward(default_prng(), &config, config.passes).is_none());
+    }
+
+    #[test]
+    fn no_pass_if_regression_fails() {
+        let mut config = default_config();
+        config.regressions = vec![regression(123)];
+        let report = run(default_prng(), &config, |_| panic!());
+        let counterexample = report.counterexample.unwrap();
+        assert_eq!(counterexample.pass, None);
+    }
+
     #[test]
     fn has_hints_if_enabled_and_test_deterministic() {
         let config = Config {
@@ -0,0 +1,161 @@
+//! Provides a runner function that samples a test.
+//!
+//! This runner function can be used for previewing the test data generated by a test. Each
+//! sample uses the [`Prng`] that [`runner::repeatedly::run`] would use for the pass with the
+//! same index. The limits are interpolated over the samples, hence they only match the limits of
+//! the passes if the number of samples equals the number of passes.
+//!
+//! The test is run as is, including its assertions. A failed test run does not abort the
+//! sampling.
+//!
+//! [`runner::repeatedly::run`]: crate::runner::repeatedly::run
+
+use std::panic::{RefUnwindSafe, UnwindSafe, catch_unwind};
+
+use crate::hints::Hints;
+use crate::runner::{self, Error, LimitSeries};
+use crate::stats::Stats;
+use crate::{Fate, Limit, Prng};
+
+/// The configuration for sampling a test.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The upper limit for the length of generated dynamic data structures used for the first
+    /// sample. The following samples use an interpolated limit between [`start_limit`] and
+    /// [`end_limit`].
+    ///
+    /// [`start_limit`]: Config::start_limit
+    /// [`end_limit`]: Config::end_limit
+    pub start_limit: Limit,
+    /// The upper limit for the length of generated dynamic data structures used for the last
+    /// sample.
+    pub end_limit: Limit,
+    /// Defines how many times the test will be run.
+    pub samples: u64,
+}
+
+/// A single test run.
+#[derive(Debug)]
+pub struct Sample {
+    /// The limit for dynamic data structures the test run has used.
+    pub limit: Limit,
+    /// The hints collected during the test run. It's defined if and only if the feature `hints`
+    /// is present.
+    pub hints: Option<Hints>,
+    /// The error occurred during the test run. It's defined if and only the test has panicked.
+    pub error: Option<Error>,
+}
+
+/// The result of sampling a test.
+#[derive(Debug)]
+pub struct Report {
+    /// The test runs in the order of their execution.
+    pub samples: Vec<Sample>,
+    /// The stats collected during all test runs. It's defined if and only if the feature
+    /// `stats` is present.
+    pub stats: Option<Stats>,
+}
+
+/// Runs the test the configured number of times with enabled hints and stats.
+///
+/// Each test run uses its own [`Prng`] that is derived from the given one in the same way as
+/// [`runner::repeatedly::run`] does. Panics are caught and added to the report.
+///
+/// [`runner::repeatedly::run`]: crate::runner::repeatedly::run
+pub fn run<T>(prng: Prng, config: &Config, test: T) -> Report
+where
+    T: Fn(Fate) + UnwindSafe + RefUnwindSafe,
+{
+    let limit_series = LimitSeries::new(config.start_limit, config.end_limit, config.samples);
+    let hints_enabled = cfg!(feature = "hints");
+    let stats_enabled = cfg!(feature = "stats");
+
+    let test = &test;
+    let (samples, stats) = runner::util::collect_stats(stats_enabled, || {
+        (0..)
+            .zip(limit_series.into_iter())
+            .map(|(sample, limit)| {
+                let mut sample_prng = runner::repeatedly::pass_prng(&prng, sample);
+                let (test_result, hints) = runner::util::collect_hints(hints_enabled, || {
+                    catch_unwind(move || {
+                        let fate = Fate::new(&mut sample_prng, limit);
+                        test(fate)
+                    })
+                });
+                Sample {
+                    limit,
+                    hints,
+                    error: test_result.err().map(Error),
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    Report { samples, stats }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::runner::sample::{Config, run};
+    use crate::{Prng, Seed, runner};
+
+    #[test]
+    fn runs_test_for_each_sample_even_if_it_fails() {
+        let config = Config {
+            start_limit: 0.into(),
+            end_limit: 10.into(),
+            samples: 11,
+        };
+        let report = run(Prng::from_seed(Seed(42)), &config, |fate| {
+            hint!("limit is {}", fate.limit().0);
+            assert!(fate.limit().0 % 2 == 0);
+        });
+
+        assert_eq!(report.samples.len(), 11);
+        for (index, sample) in report.samples.iter().enumerate() {
+            assert_eq!(sample.limit.0, index as u64);
+            assert_eq!(sample.error.is_some(), index % 2 == 1);
+            if cfg!(feature = "hints") {
+                let hints = sample.hints.as_ref().unwrap();
+                assert_eq!(hints.0[0].text, format!("limit is {index}"));
+            }
+        }
+    }
+
+    #[test]
+    fn samples_match_passes_of_repeatedly() {
+        let sample_config = Config {
+            start_limit: 0.into(),
+            end_limit: 100.into(),
+            samples: 10,
+        };
+        let repeatedly_config = runner::repeatedly::Config {
+            regressions: Vec::new(),
+            start_limit: 0.into(),
+            end_limit: 100.into(),
+            passes: 10,
+            hints_enabled: false,
+            stats_enabled: false,
+        };
+        let prng = Prng::from_seed(Seed(42));
+
+        let report = run(prng.clone(), &sample_config, |mut fate| {
+            hint_debug!((fate.roll(dice::u64(..))));
+        });
+
+        if cfg!(feature = "hints") {
+            for (pass, sample) in (0..).zip(report.samples) {
+                let regression =
+                    runner::repeatedly::fast_forward(prng.clone(), &repeatedly_config, pass)
+                        .unwrap();
+                let mut regression_prng = regression.prng;
+                let expected =
+                    Fate::new(&mut regression_prng, regression.limit).roll(dice::u64(..));
+
+                let hints = sample.hints.unwrap();
+                assert!(hints.0[0].text.ends_with(&format!("= {expected:?}")));
+            }
+        }
+    }
+}
@@ -0,0 +1,603 @@
+//! Statistical tests for validating the distribution of generators.
+//!
+//! Each test rolls a [`Die`] many times and returns a p-value. A p-value is the probability
+//! that a correct generator produces a result at least as extreme as the observed one. A tiny
+//! p-value indicates that the generator does not follow the expected distribution.
+//!
+//! Even a correct generator produces small p-values once in a while. For avoiding flaky tests
+//! you should compare the p-value with a very small threshold, e.g. `1e-6`.
+//!
+//! # Example
+//!
+//! ```
+//! use dicetest::prelude::*;
+//! use dicetest::statistics;
+//!
+//! Dicetest::once().run(|fate| {
+//!     let die = dice::uni_u8(0..4);
+//!     let expected = [0.25; 4];
+//!     let p_value = statistics::chi_squared(fate, die, 1000, &expected, |&x| x as usize);
+//!     assert!(p_value > 1e-6);
+//! });
+//! ```
+
+use std::f64::consts::SQRT_2;
+
+use crate::prelude::*;
+
+/// Performs a chi-squared goodness of fit test for a discrete distribution.
+///
+/// The die is rolled `samples` times. The function `category` assigns each value to a
+/// category that is used as index for `expected`. The slice `expected` contains the expected
+/// probability of each category, hence its elements must sum up to one.
+///
+/// Categories with probability zero are impossible. If such a category is observed, the
+/// p-value is zero. Otherwise they are ignored, i.e. they don't count as degree of freedom.
+/// The p-value is one if there is only a single possible category.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero, if `category` returns an index that is out of bounds of
+/// `expected` or if `expected` contains less than two categories.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::statistics;
+///
+/// Dicetest::once().run(|fate| {
+///     let die = dice::weighted_one_of().two((1, false), (3, true));
+///     let expected = [0.25, 0.75];
+///     let p_value = statistics::chi_squared(fate, die, 1000, &expected, |&b| usize::from(b));
+///     assert!(p_value > 1e-6);
+/// });
+/// ```
+pub fn chi_squared<T>(
+    mut fate: Fate,
+    die: impl Die<T>,
+    samples: u64,
+    expected: &[f64],
+    category: impl Fn(&T) -> usize,
+) -> f64 {
+    assert!(
+        expected.len() >= 2,
+        "The chi-squared test needs at least two categories"
+    );
+    assert!(
+        samples > 0,
+        "The chi-squared test needs at least one sample"
+    );
+
+    let mut observed = vec![0u64; expected.len()];
+    for _ in 0..samples {
+        let value = fate.roll(&die);
+        let index = category(&value);
+        assert!(
+            index < expected.len(),
+            "Category {} is out of bounds, there are only {} categories",
+            index,
+            expected.len()
+        );
+        observed[index] += 1;
+    }
+
+    let observed_impossible_category = observed
+        .iter()
+        .zip(expected)
+        .any(|(&observed, &probability)| probability <= 0.0 && observed > 0);
+    if observed_impossible_category {
+        return 0.0;
+    }
+
+    let possible_categories = expected
+        .iter()
+        .filter(|&&probability| probability > 0.0)
+        .count();
+    if possible_categories < 2 {
+        return 1.0;
+    }
+
+    let statistic = observed
+        .iter()
+        .zip(expected)
+        .filter(|&(_, &probability)| probability > 0.0)
+        .map(|(&observed, &probability)| {
+            let expected = samples as f64 * probability;
+            (observed as f64 - expected).powi(2) / expected
+        })
+        .sum::<f64>();
+    let degrees_of_freedom = (possible_categories - 1) as f64;
+
+    regularized_gamma_q(degrees_of_freedom / 2.0, statistic / 2.0)
+}
+
+/// Performs a Kolmogorov–Smirnov test for a continuous distribution.
+///
+/// The die is rolled `samples` times. The function `cdf` is the cumulative distribution
+/// function of the expected distribution.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::statistics;
+///
+/// Dicetest::once().run(|fate| {
+///     let die = dice::unit_f64();
+///     let p_value = statistics::kolmogorov_smirnov(fate, die, 1000, |x| x.clamp(0.0, 1.0));
+///     assert!(p_value > 1e-6);
+/// });
+/// ```
+pub fn kolmogorov_smirnov(
+    mut fate: Fate,
+    die: impl Die<f64>,
+    samples: u64,
+    cdf: impl Fn(f64) -> f64,
+) -> f64 {
+    let mut values = (0..samples).map(|_| fate.roll(&die)).collect::<Vec<_>>();
+    values.sort_by(f64::total_cmp);
+
+    let n = values.len() as f64;
+    let distance = values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            let expected = cdf(value);
+            let lower = index as f64 / n;
+            let upper = (index + 1) as f64 / n;
+            (expected - lower).max(upper - expected)
+        })
+        .fold(0.0, f64::max);
+
+    let sqrt_n = n.sqrt();
+    kolmogorov_q((sqrt_n + 0.12 + 0.11 / sqrt_n) * distance)
+}
+
+/// Performs a Wald–Wolfowitz runs test for independence.
+///
+/// The die is rolled `samples` times. Each value is classified as above or below the given
+/// median, values equal to the median are ignored. The test checks whether the number of runs,
+/// i.e. maximal sequences of values on the same side, is plausible for independent values.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::statistics;
+///
+/// Dicetest::once().run(|fate| {
+///     let p_value = statistics::runs(fate, dice::unit_f64(), 1000, 0.5);
+///     assert!(p_value > 1e-6);
+/// });
+/// ```
+pub fn runs(mut fate: Fate, die: impl Die<f64>, samples: u64, median: f64) -> f64 {
+    let sides = (0..samples)
+        .map(|_| fate.roll(&die))
+        .filter(|&value| value != median)
+        .map(|value| value > median)
+        .collect::<Vec<_>>();
+
+    let above = sides.iter().filter(|&&above| above).count() as f64;
+    let below = sides.len() as f64 - above;
+    if above == 0.0 || below == 0.0 {
+        // All values are on the same side, which is very unlikely for independent values
+        return if sides.len() <= 1 { 1.0 } else { 0.0 };
+    }
+
+    let runs = 1 + sides.windows(2).filter(|pair| pair[0] != pair[1]).count();
+
+    let n = above + below;
+    let mean = 2.0 * above * below / n + 1.0;
+    let variance = 2.0 * above * below * (2.0 * above * below - n) / (n * n * (n - 1.0));
+    if variance <= 0.0 {
+        return 1.0;
+    }
+
+    normal_two_sided_p((runs as f64 - mean) / variance.sqrt())
+}
+
+/// Performs a test for the lag-1 serial correlation.
+///
+/// The die is rolled `samples` times. The test checks whether the correlation between
+/// consecutive values is plausible for independent values.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::statistics;
+///
+/// Dicetest::once().run(|fate| {
+///     let p_value = statistics::serial_correlation(fate, dice::unit_f64(), 1000);
+///     assert!(p_value > 1e-6);
+/// });
+/// ```
+pub fn serial_correlation(mut fate: Fate, die: impl Die<f64>, samples: u64) -> f64 {
+    let values = (0..samples).map(|_| fate.roll(&die)).collect::<Vec<_>>();
+    if values.len() < 3 {
+        return 1.0;
+    }
+
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    if variance == 0.0 {
+        // Constant values are perfectly correlated
+        return 0.0;
+    }
+    let covariance = values
+        .windows(2)
+        .map(|pair| (pair[0] - mean) * (pair[1] - mean))
+        .sum::<f64>();
+    let correlation = covariance / variance;
+
+    // For independent values the correlation is approximately normal distributed with
+    // mean -1/n and variance 1/n.
+    normal_two_sided_p((correlation + 1.0 / n) * n.sqrt())
+}
+
+/// Performs Marsaglia's birthday spacings test.
+///
+/// The die is rolled `samples` times, each value is interpreted as a birthday in a year with
+/// `days` days. The number of repeated spacings between the sorted birthdays is approximately
+/// Poisson distributed with mean `samples^3 / (4 * days)`. The parameters should be chosen so
+/// that the mean is small, e.g. 512 samples and 2^24 days.
+///
+/// # Panics
+///
+/// Panics if `days` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use dicetest::prelude::*;
+/// use dicetest::statistics;
+///
+/// Dicetest::once().run(|fate| {
+///     let days = 1 << 24;
+///     let die = dice::uni_u64(0..days);
+///     let p_value = statistics::birthday_spacings(fate, die, 512, days);
+///     assert!(p_value > 1e-6);
+/// });
+/// ```
+pub fn birthday_spacings(mut fate: Fate, die: impl Die<u64>, samples: u64, days: u64) -> f64 {
+    assert!(
+        days > 0,
+        "The birthday spacings test needs at least one day"
+    );
+
+    let mut birthdays = (0..samples)
+        .map(|_| fate.roll(&die) % days)
+        .collect::<Vec<_>>();
+    birthdays.sort_unstable();
+
+    let mut spacings = birthdays
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect::<Vec<_>>();
+    spacings.sort_unstable();
+    let repeated = spacings
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1])
+        .count() as u64;
+
+    let mean = (samples as f64).powi(3) / (4.0 * days as f64);
+    poisson_two_sided_p(repeated, mean)
+}
+
+// The two-sided p-value of a standard normal distributed statistic.
+fn normal_two_sided_p(z: f64) -> f64 {
+    erfc(z.abs() / SQRT_2).min(1.0)
+}
+
+// The two-sided p-value of a Poisson distributed statistic.
+fn poisson_two_sided_p(k: u64, mean: f64) -> f64 {
+    let mut probability = (-mean).exp();
+    let mut lower_tail = 0.0;
+    for i in 0..=k {
+        if i > 0 {
+            probability *= mean / i as f64;
+        }
+        lower_tail += probability;
+    }
+    // The upper tail includes the probability of k itself
+    let upper_tail = 1.0 - lower_tail + probability;
+    (2.0 * lower_tail.min(upper_tail)).min(1.0)
+}
+
+// The complementary error function with a fractional error below 1.2e-7.
+//
+// See Numerical Recipes, chapter 6.2.
+pub(crate) fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * polynomial.exp();
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+// The logarithm of the gamma function, using the Lanczos approximation.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+// The regularized upper incomplete gamma function Q(a, x).
+//
+// See Numerical Recipes, chapter 6.2.
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 1000;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        // Series representation of P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefactor).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction representation of Q(a, x) with the modified Lentz's method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..=MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (prefactor * h).clamp(0.0, 1.0)
+    }
+}
+
+// The complementary cumulative distribution function of the Kolmogorov distribution.
+//
+// See Numerical Recipes, chapter 14.3.
+fn kolmogorov_q(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        // The series converges slowly, but the result is almost one
+        return 1.0;
+    }
+
+    let mut sum = 0.0;
+    let mut sign = 2.0;
+    let mut previous_term = 0.0f64;
+    for j in 1..=100 {
+        let j = j as f64;
+        let term = sign * (-2.0 * j * j * lambda * lambda).exp();
+        sum += term;
+        if term.abs() <= 1e-3 * previous_term || term.abs() <= 1e-8 * sum {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+        previous_term = term.abs();
+    }
+    1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::prelude::*;
+    use crate::statistics::{self, erfc, kolmogorov_q, ln_gamma, regularized_gamma_q};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{actual} is not close to {expected}"
+        );
+    }
+
+    #[test]
+    fn math_examples() {
+        assert_close(ln_gamma(1.0), 0.0);
+        assert_close(ln_gamma(5.0), 24f64.ln());
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln());
+        assert_close(erfc(0.0), 1.0);
+        assert_close(erfc(1.0), 0.157_299_207);
+        assert_close(erfc(-1.0), 1.842_700_793);
+        // The chi-squared distribution with 2 degrees of freedom has Q = exp(-x/2)
+        assert_close(regularized_gamma_q(1.0, 1.5), (-1.5f64).exp());
+        // The critical value of the chi-squared distribution with 10 degrees of freedom for 5%
+        assert!((regularized_gamma_q(5.0, 18.307 / 2.0) - 0.05).abs() < 1e-4);
+        // The critical value of the Kolmogorov distribution for 5%
+        assert!((kolmogorov_q(1.358) - 0.05).abs() < 1e-3);
+    }
+
+    #[test]
+    fn chi_squared_accepts_uniform_die() {
+        Dicetest::once().run(|fate| {
+            let expected = [1.0 / 16.0; 16];
+            let p_value =
+                statistics::chi_squared(fate, dice::uni_u8(..), 10_000, &expected, |&x| {
+                    usize::from(x >> 4)
+                });
+            assert!(p_value > 1e-6, "p-value is {p_value}");
+        })
+    }
+
+    #[test]
+    fn chi_squared_rejects_biased_die() {
+        Dicetest::once().run(|fate| {
+            let die = dice::weighted_one_of().two((1, 0), (2, 1));
+            let p_value = statistics::chi_squared(fate, die, 10_000, &[0.5, 0.5], |&x| x);
+            assert!(p_value < 1e-6, "p-value is {p_value}");
+        })
+    }
+
+    #[test]
+    fn chi_squared_rejects_impossible_category() {
+        Dicetest::once().run(|mut fate| {
+            // About every thousandth value falls into the impossible category
+            let category = |&x: &u64| match x % 1000 {
+                0 => 2,
+                _ => (x % 2) as usize,
+            };
+            let expected = [0.5, 0.5, 0.0];
+            let p_value = statistics::chi_squared(
+                fate.copy(),
+                dice::uni_u64(..),
+                20_000,
+                &expected,
+                category,
+            );
+            assert_eq!(p_value, 0.0);
+
+            let p_value = statistics::chi_squared(fate, dice::just(2), 10, &expected, |&x| x);
+            assert_eq!(p_value, 0.0);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "The chi-squared test needs at least one sample")]
+    fn chi_squared_panics_without_samples() {
+        Dicetest::once().run(|fate| {
+            statistics::chi_squared(fate, dice::just(0), 0, &[0.5, 0.5], |&x| x);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Category 2 is out of bounds, there are only 2 categories")]
+    fn chi_squared_panics_if_category_is_out_of_bounds() {
+        Dicetest::once().run(|fate| {
+            statistics::chi_squared(fate, dice::just(2), 10, &[0.5, 0.5], |&x| x);
+        })
+    }
+
+    #[test]
+    fn chi_squared_ignores_unobserved_impossible_category() {
+        Dicetest::once().run(|mut fate| {
+            let prng = fate.fork_prng();
+            let limit = fate.limit();
+            let p_value = |expected: &[f64]| {
+                let mut prng = prng.clone();
+                let fate = Fate::new(&mut prng, limit);
+                statistics::chi_squared(fate, dice::uni_u8(..), 1000, expected, |&x| {
+                    usize::from(x >> 7)
+                })
+            };
+            assert_eq!(p_value(&[0.5, 0.5, 0.0]), p_value(&[0.5, 0.5]));
+
+            let p_value = statistics::chi_squared(fate, dice::just(0), 10, &[1.0, 0.0], |&x| x);
+            assert_eq!(p_value, 1.0);
+        })
+    }
+
+    #[test]
+    fn kolmogorov_smirnov_rejects_wrong_distribution() {
+        Dicetest::once().run(|fate| {
+            let die = dice::unit_f64().map(|x| x * x);
+            let p_value = statistics::kolmogorov_smirnov(fate, die, 10_000, |x| x);
+            assert!(p_value < 1e-6, "p-value is {p_value}");
+        })
+    }
+
+    #[test]
+    fn runs_rejects_dependent_values() {
+        Dicetest::once().run(|fate| {
+            // Each value is on the other side of the median than the previous one
+            let above = Cell::new(false);
+            let die = dice::from_fn(|mut fate| {
+                above.set(!above.get());
+                let x = fate.roll(dice::unit_f64()) / 2.0;
+                if above.get() { 0.5 + x } else { x }
+            });
+            let p_value = statistics::runs(fate, die, 1000, 0.5);
+            assert!(p_value < 1e-6, "p-value is {p_value}");
+        })
+    }
+
+    #[test]
+    fn serial_correlation_rejects_constant_die() {
+        Dicetest::once().run(|fate| {
+            let p_value = statistics::serial_correlation(fate, dice::just(1.0), 1000);
+            assert!(p_value < 1e-6, "p-value is {p_value}");
+        })
+    }
+
+    #[test]
+    fn birthday_spacings_rejects_clustered_die() {
+        Dicetest::once().run(|fate| {
+            // The birthdays use only every 1024th day
+            let die = dice::uni_u64(0..1 << 14).map(|x| x << 10);
+            let p_value = statistics::birthday_spacings(fate, die, 512, 1 << 24);
+            assert!(p_value < 1e-6, "p-value is {p_value}");
+        })
+    }
+
+    #[test]
+    fn tests_accept_unit_f64() {
+        Dicetest::once().run(|mut fate| {
+            let kolmogorov_smirnov =
+                statistics::kolmogorov_smirnov(fate.copy(), dice::unit_f64(), 10_000, |x| x);
+            let runs = statistics::runs(fate.copy(), dice::unit_f64(), 10_000, 0.5);
+            let serial_correlation =
+                statistics::serial_correlation(fate.copy(), dice::unit_f64(), 10_000);
+            let birthday_spacings =
+                statistics::birthday_spacings(fate.copy(), dice::uni_u64(..), 512, 1 << 24);
+
+            hint_debug!(kolmogorov_smirnov);
+            hint_debug!(runs);
+            hint_debug!(serial_correlation);
+            hint_debug!(birthday_spacings);
+
+            assert!(kolmogorov_smirnov > 1e-6);
+            assert!(runs > 1e-6);
+            assert!(serial_correlation > 1e-6);
+            assert!(birthday_spacings > 1e-6);
+        })
+    }
+}
@@ -14,14 +14,14 @@ pub fn bytes_to_u64(bytes: [u8; 8]) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::asserts;
+    use crate::laws;
     use crate::prelude::*;
     use crate::util::conversion;
 
     #[test]
     fn bytes_to_u64_is_left_inverse() {
         Dicetest::repeatedly().run(|fate| {
-            asserts::left_inverse(
+            laws::left_inverse(
                 fate,
                 dice::array(dice::u8(..)),
                 conversion::bytes_to_u64,
@@ -33,7 +33,7 @@ mod tests {
     #[test]
     fn u64_to_bytes_is_left_inverse() {
         Dicetest::repeatedly().run(|fate| {
-            asserts::left_inverse(
+            laws::left_inverse(
                 fate,
                 dice::u64(..),
                 conversion::u64_to_bytes,
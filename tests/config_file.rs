@@ -0,0 +1,130 @@
+// Tests the overriding of the test parameters via config file and environment variables.
+//
+// The parameters are read from the environment of the process, hence each scenario runs one of
+// the child tests in a separate process of this test binary.
+
+use std::process::{Command, Output};
+use std::{env, fs};
+
+use dicetest::prelude::*;
+
+// The child tests only run if this environment variable is set.
+const CHILD_KEY: &str = "CONFIG_FILE_TEST_CHILD";
+
+const CONFIG_FILE: &str = r#"
+passes = 3
+
+[profile.quick]
+passes = 5
+
+[test."child_with_test_section"]
+passes = 7
+"#;
+
+#[test]
+fn child_with_test_section() {
+    if env::var_os(CHILD_KEY).is_some() {
+        Dicetest::repeatedly().passes(1).run(|_| ());
+    }
+}
+
+#[test]
+fn child_without_test_section() {
+    if env::var_os(CHILD_KEY).is_some() {
+        Dicetest::repeatedly().passes(1).run(|_| ());
+    }
+}
+
+// Runs the child test in a separate process whose manifest directory contains the config file.
+fn run_child(child: &str, vars: &[(&str, &str)]) -> Output {
+    let manifest_dir = env::temp_dir().join(format!(
+        "dicetest-config-file-{}-{child}-{}",
+        std::process::id(),
+        vars.iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("-"),
+    ));
+    fs::create_dir_all(&manifest_dir).unwrap();
+    fs::write(manifest_dir.join("dicetest.toml"), CONFIG_FILE).unwrap();
+
+    let mut command = Command::new(env::current_exe().unwrap());
+    command
+        .args(["--exact", child, "--nocapture"])
+        .env(CHILD_KEY, "1")
+        .env("CARGO_MANIFEST_DIR", &manifest_dir);
+
+    // The environment of this process must not influence the child
+    for (key, _) in env::vars_os() {
+        if key.to_string_lossy().starts_with("DICETEST_") {
+            command.env_remove(key);
+        }
+    }
+    command.envs(vars.iter().copied());
+
+    let output = command.output().unwrap();
+    fs::remove_dir_all(&manifest_dir).unwrap();
+    output
+}
+
+fn assert_passes(output: &Output, passes: u64) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}\n{stderr}");
+    assert!(
+        stdout.contains(&format!("The test withstood {passes} passes.")),
+        "{stdout}"
+    );
+}
+
+fn assert_fails_with(output: &Output, message: &str) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stdout}\n{stderr}");
+    assert!(stderr.contains(message), "{stderr}");
+}
+
+#[test]
+fn top_level_keys_override_source_code() {
+    let output = run_child("child_without_test_section", &[]);
+    assert_passes(&output, 3);
+}
+
+#[test]
+fn profile_overrides_top_level_keys() {
+    let output = run_child(
+        "child_without_test_section",
+        &[("DICETEST_PROFILE", "quick")],
+    );
+    assert_passes(&output, 5);
+}
+
+#[test]
+fn test_section_overrides_profile() {
+    let output = run_child("child_with_test_section", &[("DICETEST_PROFILE", "quick")]);
+    assert_passes(&output, 7);
+}
+
+#[test]
+fn env_overrides_test_section() {
+    let output = run_child(
+        "child_with_test_section",
+        &[("DICETEST_PROFILE", "quick"), ("DICETEST_PASSES", "9")],
+    );
+    assert_passes(&output, 9);
+}
+
+#[test]
+fn unknown_profile_is_rejected() {
+    let output = run_child(
+        "child_without_test_section",
+        &[("DICETEST_PROFILE", "slow")],
+    );
+    assert_fails_with(&output, "Profile 'slow' is not defined");
+}
+
+#[test]
+fn unknown_env_key_is_rejected() {
+    let output = run_child("child_without_test_section", &[("DICETEST_PASSE", "9")]);
+    assert_fails_with(&output, "Environment variable 'DICETEST_PASSE' is unknown");
+}
@@ -0,0 +1,75 @@
+// Tests the reports of the tests defined with `dicetest!`.
+//
+// A failing test panics, hence each scenario runs one of the child tests in a separate process
+// of this test binary and inspects its output.
+
+use std::env;
+use std::process::{Command, Output};
+
+use dicetest::prelude::*;
+
+// The child tests only run if this environment variable is set.
+const CHILD_KEY: &str = "MACRO_DICETEST_TEST_CHILD";
+
+fn is_child() -> bool {
+    env::var_os(CHILD_KEY).is_some()
+}
+
+dicetest! {
+    #[dicetest(seed = Some(1234.into()))]
+    fn child_fails(x: u8 = dice::just(42), name: String = dice::just("foo".to_owned())) {
+        if is_child() {
+            assert_ne!(x, 42, "{name}");
+        }
+    }
+
+    #[dicetest(passes = 7)]
+    fn child_passes(_x: u8) {}
+}
+
+// Runs the child test in a separate process.
+fn run_child(child: &str) -> Output {
+    let mut command = Command::new(env::current_exe().unwrap());
+    command
+        .args(["--exact", child, "--nocapture"])
+        .env(CHILD_KEY, "1");
+
+    // The environment of this process must not influence the child
+    for (key, _) in env::vars_os() {
+        if key.to_string_lossy().starts_with("DICETEST_") {
+            command.env_remove(key);
+        }
+    }
+
+    command.output().unwrap()
+}
+
+#[test]
+fn failure_report_contains_parameters() {
+    let output = run_child("child_fails");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success(), "{stdout}");
+    assert!(stdout.contains("# Counterexample"), "{stdout}");
+    if cfg!(feature = "hints") {
+        assert!(stdout.contains("\t- x = 42\n"), "{stdout}");
+        assert!(stdout.contains("\t- name = \"foo\"\n"), "{stdout}");
+    }
+}
+
+#[test]
+fn seed_option_takes_effect() {
+    let output = run_child("child_fails");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("- seed: 1234\n"), "{stdout}");
+}
+
+#[test]
+fn passes_option_takes_effect() {
+    let output = run_child("child_passes");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("The test withstood 7 passes."), "{stdout}");
+}